@@ -1,3 +1,43 @@
 pub const MAX_SIGNERS: usize = 10;
 pub const MAX_INSTRUCTIONS: usize = 5;
-pub const VAULT_SEED: &[u8] = b"vault";
\ No newline at end of file
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+pub const ARCHIVE_SEED: &[u8] = b"archive";
+pub const OFFCHAIN_APPROVAL_SEED: &[u8] = b"offchain-approval";
+pub const RECURRING_PAYMENT_SEED: &[u8] = b"recurring";
+pub const SPENDING_LIMIT_SEED: &[u8] = b"spending-limit";
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+pub const CLOSED_WALLET_SEED: &[u8] = b"closed-wallet";
+pub const MAX_TITLE_LEN: usize = 64;
+pub const MAX_MEMO_LEN: usize = 256;
+pub const MAX_LABEL_LEN: usize = 32;
+pub const RECENT_EXECUTIONS_LEN: usize = 8;
+pub const MAX_ALLOWED_DESTINATIONS: usize = 16;
+pub const MAX_ALLOWED_PROGRAMS: usize = 8;
+pub const PROGRAM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Capability flags reported by get_program_info
+pub const FEATURE_ARBITRARY_EXECUTION: u64 = 1 << 0;
+pub const FEATURE_TRANSACTION_TITLE: u64 = 1 << 1;
+pub const FEATURE_PROPOSAL_PAUSE: u64 = 1 << 2;
+pub const FEATURE_APPROVAL_RECEIPTS: u64 = 1 << 3;
+pub const FEATURE_TIMELOCK: u64 = 1 << 4;
+pub const FEATURE_OFFCHAIN_APPROVAL: u64 = 1 << 5;
+pub const FEATURE_AUTO_EXECUTE: u64 = 1 << 6;
+pub const FEATURE_RECURRING_PAYMENTS: u64 = 1 << 7;
+pub const FEATURE_SPENDING_LIMITS: u64 = 1 << 8;
+pub const FEATURE_DESTINATION_ALLOWLIST: u64 = 1 << 9;
+pub const FEATURE_PROGRAM_ALLOWLIST: u64 = 1 << 10;
+pub const FEATURE_REJECTIONS: u64 = 1 << 11;
+pub const SUPPORTED_FEATURES: u64 = FEATURE_ARBITRARY_EXECUTION
+    | FEATURE_TRANSACTION_TITLE
+    | FEATURE_PROPOSAL_PAUSE
+    | FEATURE_APPROVAL_RECEIPTS
+    | FEATURE_TIMELOCK
+    | FEATURE_OFFCHAIN_APPROVAL
+    | FEATURE_AUTO_EXECUTE
+    | FEATURE_RECURRING_PAYMENTS
+    | FEATURE_SPENDING_LIMITS
+    | FEATURE_DESTINATION_ALLOWLIST
+    | FEATURE_PROGRAM_ALLOWLIST
+    | FEATURE_REJECTIONS;
@@ -42,8 +42,88 @@ pub enum ErrorCode {
     AccountNotFound,
     #[msg("Transaction not executed yet")]
     TransactionNotExecuted,
-    #[msg("Only transaction creator can close it")]
+    #[msg("Only the creator or an owner can close the transaction")]
     UnauthorizedClose,
     #[msg("Instruction data too large")]
     DataTooLarge,
+    #[msg("No funds to reclaim")]
+    NoStrayFunds,
+    #[msg("Title too long")]
+    TitleTooLong,
+    #[msg("New proposals are paused")]
+    ProposalsPaused,
+    #[msg("Only transaction creator can execute it")]
+    UnauthorizedExecutor,
+    #[msg("Invalid dependency transaction")]
+    InvalidDependency,
+    #[msg("Dependency transaction not executed yet")]
+    DependencyNotMet,
+    #[msg("Only transaction creator can amend it")]
+    UnauthorizedAmend,
+    #[msg("Transaction already approved by other owners")]
+    AlreadyApproved,
+    #[msg("No instructions provided")]
+    NoInstructions,
+    #[msg("Owner already exists")]
+    OwnerAlreadyExists,
+    #[msg("Too many owners")]
+    TooManyOwners,
+    #[msg("Owner is quarantined")]
+    OwnerQuarantined,
+    #[msg("Invalid off-chain approval")]
+    InvalidOffchainApproval,
+    #[msg("Owner label too long")]
+    InvalidNameLength,
+    #[msg("A key rotation is already pending")]
+    KeyRotationPending,
+    #[msg("No key rotation is pending")]
+    NoKeyRotation,
+    #[msg("Not allowed to cancel this key rotation")]
+    UnauthorizedKeyRotation,
+    #[msg("Owner count below the wallet minimum")]
+    TooFewOwners,
+    #[msg("Transaction eta must be in the future")]
+    InvalidEta,
+    #[msg("Transaction timelock has not expired")]
+    TimelockNotExpired,
+    #[msg("Execution delay cannot be negative")]
+    InvalidExecutionDelay,
+    #[msg("Invalid recurring payment schedule")]
+    InvalidRecurringPayment,
+    #[msg("Recurring payment is not due yet")]
+    RecurringPaymentNotDue,
+    #[msg("Recurring payment is cancelled or finished")]
+    RecurringPaymentFinished,
+    #[msg("Recurring payment is still active")]
+    RecurringPaymentActive,
+    #[msg("Invalid spending limit")]
+    InvalidSpendingLimit,
+    #[msg("Spending limit exceeded")]
+    SpendingLimitExceeded,
+    #[msg("Destination is not on the allowlist")]
+    DestinationNotAllowed,
+    #[msg("Destination is already on the allowlist")]
+    DuplicateDestination,
+    #[msg("Allowlist is full")]
+    AllowlistFull,
+    #[msg("Program is not on the allowlist")]
+    ProgramNotAllowed,
+    #[msg("Program is already on the allowlist")]
+    DuplicateProgram,
+    #[msg("Owner has already rejected this transaction")]
+    AlreadyRejected,
+    #[msg("Transaction was cancelled")]
+    TransactionCancelled,
+    #[msg("Rent must be returned to the transaction creator")]
+    InvalidRentRecipient,
+    #[msg("Wallet was closed and cannot be created again")]
+    WalletClosed,
+    #[msg("Account cannot shrink below its data")]
+    InvalidAccountState,
+    #[msg("Wallet is not in the legacy layout")]
+    NotLegacyWallet,
+    #[msg("Owner expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Wallet has reached its maximum number of transactions")]
+    TransactionLimitReached,
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct TransactionCreated {
+    pub wallet: Pubkey,
+    pub transaction: Pubkey,
+    pub creator: Pubkey,
+    pub owner_set_seqno: u32,
+    pub title: String,
+}
+
+#[event]
+pub struct TransactionExecuted {
+    pub wallet: Pubkey,
+    pub transaction: Pubkey,
+    pub executor: Pubkey,
+    pub final_signer: Option<Pubkey>,
+    pub owner_set_seqno: u32,
+}
+
+// Rejections left too little weight for the transaction to ever pass
+#[event]
+pub struct TransactionCancelled {
+    pub wallet: Pubkey,
+    pub transaction: Pubkey,
+    pub rejection_weight: u64,
+    pub owner_set_seqno: u32,
+}
+
+#[event]
+pub struct ThresholdChanged {
+    pub wallet: Pubkey,
+    pub old_threshold: u64,
+    pub new_threshold: u64,
+    pub owner_set_seqno: u32,
+}
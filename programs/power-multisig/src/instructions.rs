@@ -1,28 +1,558 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
 use crate::state::*;
-use crate::error::ErrorCode;
 use crate::constants::*;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ChangeThreshold<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeOwnerWeight<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeOwnerWeights<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetProposerMustExecute<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAutoExecute<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetExecutionDelay<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxTransactions<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEnforceAllowlist<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAllowedPrograms<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchChangeWeights<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOwnerLabel<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetProposalsPaused<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddOwner<'info> {
+    // Only ever grows the account, so existing data is never truncated
+    #[account(
+        mut,
+        realloc = Wallet::space(wallet.owners.len() + 1),
+        realloc::payer = vault,
+        realloc::zero = false,
+    )]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction and pays for the extra space
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateWallet<'info> {
+    /// CHECK: Still in the legacy layout, so it cannot be loaded as a Wallet; parsed by
+    /// the handler
+    #[account(mut, owner = crate::ID)]
+    pub wallet: UncheckedAccount<'info>,
+
+    // Any owner may migrate the wallet and pays for the extra space
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveOwner<'info> {
+    // Shrunk by the handler once the owner is gone; the freed rent goes to the vault
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetOwners<'info> {
+    // Resized by the handler to the new owner list, with the vault paying or receiving
+    // the rent difference
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReplaceOwner<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeKeyRotation<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptKeyRotation<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+    pub new_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelKeyRotation<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// CHECK: Vault PDA, only compared against the authority
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// The owner being rotated out, or the vault through an approved transaction
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QuarantineOwner<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ArchiveState<'info> {
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        init,
+        payer = vault,
+        space = WalletArchive::space(wallet.owners.len()),
+        seeds = [ARCHIVE_SEED, wallet.key().as_ref(), &wallet.archive_count.to_le_bytes()],
+        bump
+    )]
+    pub archive: Account<'info, WalletArchive>,
+
+    /// Vault PDA, signs through an approved transaction and pays for the archive
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseWallet<'info> {
+    #[account(mut, close = rent_recipient)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// Vault PDA, signs through an approved transaction and is emptied
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+
+    /// CHECK: Receives the vault's remaining lamports, named in the proposal
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Receives the wallet account's rent
+    #[account(mut)]
+    pub rent_recipient: SystemAccount<'info>,
+
+    /// Blocks create_wallet from reusing the address, paid for by the vault
+    #[account(
+        init,
+        payer = vault,
+        space = ClosedWallet::LEN,
+        seeds = [CLOSED_WALLET_SEED, wallet.key().as_ref()],
+        bump,
+    )]
+    pub closed_wallet: Account<'info, ClosedWallet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateRecurringPayment<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        init,
+        payer = vault,
+        space = RecurringPayment::LEN,
+        seeds = [RECURRING_PAYMENT_SEED, wallet.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub recurring_payment: Account<'info, RecurringPayment>,
+
+    /// Vault PDA, signs through an approved transaction and pays for the schedule
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecurringPayment<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        mut,
+        has_one = wallet @ ErrorCode::InvalidWallet,
+        has_one = destination @ ErrorCode::InvalidRecurringPayment
+    )]
+    pub recurring_payment: Account<'info, RecurringPayment>,
+
+    /// CHECK: Vault PDA, signs the transfer
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the schedule's destination
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecurringPayment<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet)]
+    pub recurring_payment: Account<'info, RecurringPayment>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRecurringPayment<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet, close = vault)]
+    pub recurring_payment: Account<'info, RecurringPayment>,
+
+    /// CHECK: Vault PDA, receives the rent
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: UncheckedAccount<'info>,
+}
 
 #[derive(Accounts)]
-pub struct ChangeThreshold<'info> {
-    #[account(mut)]
+#[instruction(owner: Pubkey)]
+pub struct CreateSpendingLimit<'info> {
     pub wallet: Account<'info, Wallet>,
-    pub proposer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = vault,
+        space = SpendingLimit::LEN,
+        seeds = [SPENDING_LIMIT_SEED, wallet.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub spending_limit: Account<'info, SpendingLimit>,
+
+    /// Vault PDA, signs through an approved transaction and pays for the limit
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ChangeOwnerWeight<'info> {
-    #[account(mut)]
+pub struct UpdateSpendingLimit<'info> {
     pub wallet: Account<'info, Wallet>,
-    pub proposer: Signer<'info>,
+
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet)]
+    pub spending_limit: Account<'info, SpendingLimit>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ChangeOwnerWeights<'info> {
+pub struct RemoveSpendingLimit<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet, close = vault)]
+    pub spending_limit: Account<'info, SpendingLimit>,
+
+    /// Vault PDA, signs through an approved transaction and receives the rent
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SpendFromAllowance<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        mut,
+        seeds = [SPENDING_LIMIT_SEED, wallet.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub spending_limit: Account<'info, SpendingLimit>,
+
+    pub owner: Signer<'info>,
+
+    /// CHECK: Vault PDA, signs the transfer
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Any account chosen by the owner
     #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDestinationAllowlist<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        init,
+        payer = vault,
+        space = DestinationAllowlist::LEN,
+        seeds = [ALLOWLIST_SEED, wallet.key().as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, DestinationAllowlist>,
+
+    /// Vault PDA, signs through an approved transaction and pays for the allowlist
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDestinationAllowlist<'info> {
     pub wallet: Account<'info, Wallet>,
-    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWLIST_SEED, wallet.key().as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, DestinationAllowlist>,
+
+    /// Vault PDA, signs through an approved transaction
+    #[account(
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -31,11 +561,7 @@ pub struct CreateWallet<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + // discriminator
-            4 + (OwnerConfig::LEN * owners.len()) + // owners vec with length prefix
-            8 + // threshold_weight
-            1 + // nonce
-            4   // owner_set_seqno
+        space = Wallet::space(owners.len())
     )]
     pub wallet: Account<'info, Wallet>,
 
@@ -46,6 +572,14 @@ pub struct CreateWallet<'info> {
     /// CHECK: This is a PDA that will hold SOL
     pub vault: UncheckedAccount<'info>,
 
+    /// CHECK: Only created by close_wallet; its absence shows the address was never closed
+    #[account(
+        seeds = [CLOSED_WALLET_SEED, wallet.key().as_ref()],
+        bump,
+        constraint = closed_wallet.data_is_empty() @ ErrorCode::WalletClosed,
+    )]
+    pub closed_wallet: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -58,18 +592,13 @@ pub struct CreateWallet<'info> {
     max_data_size: u16
 )]
 pub struct CreateTransaction<'info> {
+    #[account(mut)]
     pub wallet: Account<'info, Wallet>,
 
     #[account(
         init,
         payer = owner,
-        space = 8 + // discriminator
-            32 + // wallet pubkey
-            32 + // creator
-            1 + // executed
-            4 + (32 * MAX_SIGNERS) + // signers vec with length prefix
-            4 + // owner_set_seqno
-            4 + (ProposedInstruction::size(max_accounts_per_instruction as usize, max_data_size as usize) * MAX_INSTRUCTIONS) // instructions vec with length prefix
+        space = Transaction::space(max_accounts_per_instruction as usize, max_data_size as usize)
     )]
     pub transaction: Account<'info, Transaction>,
 
@@ -78,17 +607,135 @@ pub struct CreateTransaction<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct GetTransaction<'info> {
+    pub wallet: Account<'info, Wallet>,
+    #[account(constraint = transaction.wallet == wallet.key() @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+}
+
+#[derive(Accounts)]
+pub struct GetTransactionInstructions<'info> {
+    pub wallet: Account<'info, Wallet>,
+    #[account(constraint = transaction.wallet == wallet.key() @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+    #[account(seeds = [b"vault", wallet.key().as_ref()], bump = wallet.nonce)]
+    /// CHECK: Vault PDA, only its address is used
+    pub vault: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WeightGapIfSigned<'info> {
+    pub wallet: Account<'info, Wallet>,
+    #[account(constraint = transaction.wallet == wallet.key() @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+}
+
 #[derive(Accounts)]
 pub struct Approve<'info> {
+    /// Records the execution time when the approval auto-executes
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RejectTransaction<'info> {
+    pub wallet: Account<'info, Wallet>,
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveWithReceipt<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Approval receipt PDA, paid for by the approving owner
+    #[account(
+        init,
+        payer = owner,
+        space = ApprovalReceipt::LEN,
+        seeds = [RECEIPT_SEED, transaction.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, ApprovalReceipt>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetApprovalDigest<'info> {
+    pub wallet: Account<'info, Wallet>,
+    #[account(constraint = transaction.wallet == wallet.key() @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct VerifyOffchainApproval<'info> {
     pub wallet: Account<'info, Wallet>,
+
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Replay marker for this owner's off-chain approval of the transaction
+    #[account(
+        init,
+        payer = relayer,
+        space = OffchainApproval::LEN,
+        seeds = [OFFCHAIN_APPROVAL_SEED, transaction.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub approval: Account<'info, OffchainApproval>,
+
     #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSignature<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        mut,
+        has_one = wallet @ ErrorCode::InvalidWallet,
+        constraint = !transaction.executed @ ErrorCode::AlreadyExecuted,
+        constraint = !transaction.cancelled @ ErrorCode::TransactionCancelled
+    )]
     pub transaction: Account<'info, Transaction>,
+
+    /// Receipt address for this approval, closed back to the owner if it was created
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED, transaction.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Only closed when owned by this program
+    pub receipt: UncheckedAccount<'info>,
+
+    #[account(mut)]
     pub owner: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct ExecuteTransaction<'info> {
-    /// Multisig wallet account
+    /// Multisig wallet account, records the execution time
+    #[account(mut)]
     pub wallet: Account<'info, Wallet>,
 
     /// Transaction proposal account
@@ -101,10 +748,13 @@ pub struct ExecuteTransaction<'info> {
     )]
     pub transaction: Account<'info, Transaction>,
 
-    /// Executor (must be an owner and have signed)
+    /// Executor (must be an active owner and have signed)
     #[account(
         constraint = wallet.owners.iter().any(|o| o.key == owner.key()) @ ErrorCode::NotOwner,
-        constraint = transaction.signers.contains(&owner.key()) @ ErrorCode::NotSigned
+        constraint = !wallet.is_quarantined(&owner.key()) @ ErrorCode::OwnerQuarantined,
+        constraint = !wallet.is_expired(&owner.key(), Clock::get()?.unix_timestamp) @ ErrorCode::OwnerNotFound,
+        constraint = transaction.signers.contains(&owner.key()) @ ErrorCode::NotSigned,
+        constraint = !wallet.proposer_must_execute || owner.key() == transaction.creator @ ErrorCode::UnauthorizedExecutor
     )]
     pub owner: Signer<'info>,
 
@@ -120,6 +770,46 @@ pub struct ExecuteTransaction<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ApproveAndExecute<'info> {
+    /// Multisig wallet account, records the execution time
+    #[account(mut)]
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(mut, has_one = wallet @ ErrorCode::InvalidWallet)]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Approving owner, also the executor
+    pub owner: Signer<'info>,
+
+    /// CHECK: Vault PDA, signs the transaction's instructions
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AmendTransaction<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        mut,
+        has_one = wallet @ ErrorCode::InvalidWallet,
+        constraint = !transaction.executed @ ErrorCode::AlreadyExecuted,
+        constraint = !transaction.cancelled @ ErrorCode::TransactionCancelled,
+        constraint = wallet.owner_set_seqno == transaction.owner_set_seqno @ ErrorCode::OwnerSetChanged,
+        constraint = transaction.creator == creator.key() @ ErrorCode::UnauthorizedAmend
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub creator: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct CloseTransaction<'info> {
     pub wallet: Account<'info, Wallet>,
@@ -127,15 +817,89 @@ pub struct CloseTransaction<'info> {
     #[account(
         mut,
         constraint = transaction.wallet == wallet.key() @ ErrorCode::InvalidWallet,
-        constraint = transaction.executed @ ErrorCode::TransactionNotExecuted,
-        close = recipient // This will close the account after instruction execution and transfer remaining rent to recipient
+        constraint = transaction.executed || transaction.cancelled @ ErrorCode::TransactionNotExecuted,
     )]
     pub transaction: Account<'info, Transaction>,
 
-    #[account(mut)]
+    /// The creator, who paid the rent when proposing
+    #[account(mut, address = transaction.creator @ ErrorCode::InvalidRentRecipient)]
     pub recipient: SystemAccount<'info>,
 
-    // Optional: only allow transaction creator to close the account
-    #[account(constraint = owner.key() == transaction.creator @ ErrorCode::UnauthorizedClose)]
+    #[account(
+        constraint = owner.key() == transaction.creator || wallet.is_owner(&owner.key()) @ ErrorCode::UnauthorizedClose
+    )]
     pub owner: Signer<'info>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct CloseOrphanedTransaction<'info> {
+    #[account(mut, close = recipient)]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Left behind by close_wallet, proving the transaction's wallet is gone for good
+    #[account(
+        seeds = [CLOSED_WALLET_SEED, transaction.wallet.as_ref()],
+        bump,
+    )]
+    pub closed_wallet: Account<'info, ClosedWallet>,
+
+    /// The creator, who paid the rent when proposing
+    #[account(mut, address = transaction.creator @ ErrorCode::InvalidRentRecipient)]
+    pub recipient: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimStrayFunds<'info> {
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        mut,
+        constraint = transaction.wallet == wallet.key() @ ErrorCode::InvalidWallet,
+        constraint = transaction.executed || transaction.cancelled @ ErrorCode::TransactionNotExecuted,
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Vault PDA, signs through an approved transaction and receives the reclaimed lamports
+    #[account(
+        mut,
+        seeds = [b"vault", wallet.key().as_ref()],
+        bump = wallet.nonce,
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EstimateTransactionRent {}
+
+#[derive(Accounts)]
+pub struct GetProgramInfo {}
+
+#[derive(Accounts)]
+pub struct WeightedMedianOwner<'info> {
+    pub wallet: Account<'info, Wallet>,
+}
+
+#[derive(Accounts)]
+pub struct HasExecutableTransaction<'info> {
+    pub wallet: Account<'info, Wallet>,
+}
+
+#[derive(Accounts)]
+pub struct GetExecutableTransactions<'info> {
+    pub wallet: Account<'info, Wallet>,
+}
+
+#[derive(Accounts)]
+pub struct GetTimelockedTransactions<'info> {
+    pub wallet: Account<'info, Wallet>,
+}
+
+#[derive(Accounts)]
+pub struct RecentExecutionRate<'info> {
+    pub wallet: Account<'info, Wallet>,
+}
+
+#[derive(Accounts)]
+pub struct DetectNestedDoubleCounting<'info> {
+    pub wallet: Account<'info, Wallet>,
+}
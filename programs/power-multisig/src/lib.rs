@@ -1,14 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+use anchor_lang::solana_program::{
+    ed25519_program,
+    instruction::Instruction,
+    program::invoke_signed,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use anchor_lang::system_program;
 declare_id!("U8QgybKox2a31mTqKrpywzotFZ1nAqvk7erYTByDxui");
 
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use constants::*;
 use error::ErrorCode;
+use events::*;
 use instructions::*;
 use state::*;
 
@@ -20,134 +28,1317 @@ pub mod multisig_wallet {
         ctx: Context<CreateWallet>,
         owners: Vec<OwnerConfig>,
         threshold_weight: u64,
+        allow_zero_threshold: bool,
+        min_owners: u8,
+        auto_execute: bool,
+        execution_delay: i64,
     ) -> Result<()> {
         // Validate owners configuration
-        validate_owners(&owners, threshold_weight)?;
+        validate_owner_expiries(&owners)?;
+        validate_owners(&owners, threshold_weight, allow_zero_threshold)?;
+        validate_owner_labels(&owners)?;
+        require!(owners.len() >= min_owners as usize, ErrorCode::TooFewOwners);
+        require!(execution_delay >= 0, ErrorCode::InvalidExecutionDelay);
 
         let wallet = &mut ctx.accounts.wallet;
         wallet.owners = owners;
         wallet.threshold_weight = threshold_weight;
         wallet.nonce = ctx.bumps.vault;
         wallet.owner_set_seqno = 0;
+        wallet.proposals_paused = false;
+        wallet.proposer_must_execute = false;
+        wallet.allow_zero_threshold = allow_zero_threshold;
+        wallet.created_at = Clock::get()?.unix_timestamp;
+        wallet.archive_count = 0;
+        wallet.recent_executions = [0; RECENT_EXECUTIONS_LEN];
+        wallet.pending_key_rotation = None;
+        wallet.min_owners = min_owners;
+        wallet.auto_execute = auto_execute;
+        wallet.execution_delay = execution_delay;
+        wallet.enforce_allowlist = false;
+        wallet.allowed_programs = Vec::new();
+        wallet.transaction_count = 0;
+        wallet.max_transactions = 0;
 
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_transaction(
         ctx: Context<CreateTransaction>,
         instructions: Vec<ProposedInstruction>,
         max_accounts_per_instruction: u8,
         max_data_size: u16,
-    ) -> Result<()> {
+        title: String,
+        depends_on: Option<Pubkey>,
+        eta: Option<i64>,
+        memo: String,
+    ) -> Result<CreateTransactionResult> {
         // Validate transaction instructions
         validate_instructions(&instructions, max_accounts_per_instruction, max_data_size)?;
+        require!(title.len() <= MAX_TITLE_LEN, ErrorCode::TitleTooLong);
+        require!(memo.len() <= MAX_MEMO_LEN, ErrorCode::DataTooLarge);
+        let now = Clock::get()?.unix_timestamp;
+        require!(eta.map_or(true, |eta| eta > now), ErrorCode::InvalidEta);
+
+        let wallet = &mut ctx.accounts.wallet;
+        let owner = &ctx.accounts.owner;
+        require!(wallet.is_owner(&owner.key()), ErrorCode::NotOwner);
+        require!(
+            !wallet.is_quarantined(&owner.key()),
+            ErrorCode::OwnerQuarantined
+        );
+        require!(
+            !wallet.is_expired(&owner.key(), now),
+            ErrorCode::OwnerNotFound
+        );
+
+        validate_not_paused(wallet, &instructions)?;
+        validate_programs(wallet, &instructions)?;
+        validate_destinations(wallet, &instructions, ctx.remaining_accounts)?;
+        wallet.record_transaction()?;
+
+        let transaction = &mut ctx.accounts.transaction;
+
+        // The dependency must already exist under this wallet, which also rules out cycles
+        if let Some(dependency) = depends_on {
+            require_keys_neq!(dependency, transaction.key(), ErrorCode::InvalidDependency);
+            let dependency_info = find_remaining_account(ctx.remaining_accounts, &dependency)?;
+            load_dependency(dependency_info, &wallet.key())?;
+        }
+
+        transaction.initialize(
+            instructions,
+            wallet.key(),
+            owner.key(),
+            wallet.owner_set_seqno,
+            title,
+            depends_on,
+        );
+        transaction.eta = eta;
+        transaction.created_at = now;
+        transaction.memo = memo;
+
+        // The creator's signature alone may already meet the threshold
+        let current_weight = calculate_total_weight(wallet, &transaction.signers)?;
+        if current_weight >= wallet.threshold_weight {
+            transaction.final_signer = Some(owner.key());
+        }
+
+        emit!(TransactionCreated {
+            wallet: wallet.key(),
+            transaction: transaction.key(),
+            creator: owner.key(),
+            owner_set_seqno: transaction.owner_set_seqno,
+            title: transaction.title.clone(),
+        });
+
+        Ok(CreateTransactionResult {
+            transaction: transaction.key(),
+            current_weight,
+            remaining: wallet.threshold_weight.saturating_sub(current_weight),
+        })
+    }
+
+    // Replace a proposal's instructions before anyone other than the creator has signed
+    pub fn amend_transaction(
+        ctx: Context<AmendTransaction>,
+        instructions: Vec<ProposedInstruction>,
+        max_accounts_per_instruction: u8,
+        max_data_size: u16,
+    ) -> Result<()> {
+        validate_instructions(&instructions, max_accounts_per_instruction, max_data_size)?;
+
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &mut ctx.accounts.transaction;
+        validate_not_paused(wallet, &instructions)?;
+        validate_programs(wallet, &instructions)?;
+        validate_destinations(wallet, &instructions, ctx.remaining_accounts)?;
+
+        require!(
+            transaction
+                .signers
+                .iter()
+                .all(|s| *s == transaction.creator),
+            ErrorCode::AlreadyApproved
+        );
+
+        // The account was allocated at creation and cannot grow
+        let space = Transaction::space(
+            max_accounts_per_instruction as usize,
+            max_data_size as usize,
+        );
+        require!(
+            space <= transaction.to_account_info().data_len(),
+            ErrorCode::DataTooLarge
+        );
+
+        transaction.instructions = instructions;
+        // Rejections were cast against the old instructions
+        transaction.rejections.clear();
+        transaction.rejection_weight = 0;
+        Ok(())
+    }
+
+    // Read-only summary of a transaction and the governance epoch it belongs to
+    pub fn get_transaction(ctx: Context<GetTransaction>) -> Result<TransactionInfo> {
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &ctx.accounts.transaction;
+
+        Ok(TransactionInfo {
+            wallet: transaction.wallet,
+            creator: transaction.creator,
+            executed: transaction.executed,
+            signers: transaction.signers.clone(),
+            owner_set_seqno: transaction.owner_set_seqno,
+            wallet_owner_set_seqno: wallet.owner_set_seqno,
+            title: transaction.title.clone(),
+            depends_on: transaction.depends_on,
+            final_signer: transaction.final_signer,
+            eta: transaction.eta,
+            created_at: transaction.created_at,
+            rejections: transaction.rejections.clone(),
+            rejection_weight: transaction.rejection_weight,
+            cancelled: transaction.cancelled,
+            memo: transaction.memo.clone(),
+        })
+    }
+
+    // The instructions execute_transaction will invoke, for client-side simulation
+    pub fn get_transaction_instructions(
+        ctx: Context<GetTransactionInstructions>,
+    ) -> Result<Vec<ProposedInstruction>> {
+        let vault = ctx.accounts.vault.key();
+
+        ctx.accounts
+            .transaction
+            .instructions
+            .iter()
+            .map(|instruction| {
+                let ix = to_vault_instruction(instruction, &vault)?;
+                Ok(IncomingInstruction::from(ix).into())
+            })
+            .collect()
+    }
+
+    // Whether `owner` signing would make the transaction executable, and the weight
+    // still missing afterwards if not
+    pub fn weight_gap_if_signed(
+        ctx: Context<WeightGapIfSigned>,
+        owner: Pubkey,
+    ) -> Result<WeightGap> {
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &ctx.accounts.transaction;
+        require!(wallet.is_owner(&owner), ErrorCode::NotOwner);
+
+        let mut signers = transaction.signers.clone();
+        if !signers.contains(&owner) {
+            signers.push(owner);
+        }
+        let weight = calculate_total_weight(wallet, &signers)?;
+
+        Ok(WeightGap {
+            completes: weight >= wallet.threshold_weight,
+            remaining: wallet.threshold_weight.saturating_sub(weight),
+        })
+    }
+
+    // Rent a create_transaction call with the same sizing arguments will charge
+    pub fn estimate_transaction_rent(
+        _ctx: Context<EstimateTransactionRent>,
+        max_accounts_per_instruction: u8,
+        max_data_size: u16,
+    ) -> Result<u64> {
+        let space = Transaction::space(
+            max_accounts_per_instruction as usize,
+            max_data_size as usize,
+        );
+        Ok(Rent::get()?.minimum_balance(space))
+    }
+
+    // Deployed version and the optional capabilities it supports
+    pub fn get_program_info(_ctx: Context<GetProgramInfo>) -> Result<ProgramInfo> {
+        Ok(ProgramInfo {
+            version: PROGRAM_VERSION.to_string(),
+            features: SUPPORTED_FEATURES,
+        })
+    }
+
+    // Owner at which the heaviest owners first hold at least half of the total weight
+    pub fn weighted_median_owner(ctx: Context<WeightedMedianOwner>) -> Result<Pubkey> {
+        let wallet = &ctx.accounts.wallet;
+        require!(!wallet.owners.is_empty(), ErrorCode::NoOwners);
+        let total_weight = sum_owner_weights(&wallet.owners)?;
+
+        // Heaviest first, ties broken by key so the result is deterministic
+        let mut owners = wallet.owners.clone();
+        owners.sort_by(|a, b| b.weight.cmp(&a.weight).then_with(|| a.key.cmp(&b.key)));
+
+        let mut cumulative = 0u64;
+        for owner in owners.iter() {
+            cumulative = cumulative
+                .checked_add(owner.weight)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            // cumulative never exceeds total_weight, so the subtraction cannot underflow
+            if cumulative >= total_weight - cumulative {
+                return Ok(owner.key);
+            }
+        }
+
+        err!(ErrorCode::OwnerNotFound)
+    }
+
+    // Whether any of the transactions passed in remaining_accounts can be executed now
+    pub fn has_executable_transaction(ctx: Context<HasExecutableTransaction>) -> Result<bool> {
+        let wallet = &ctx.accounts.wallet;
+
+        for info in ctx.remaining_accounts.iter() {
+            if is_executable(info, wallet, ctx.remaining_accounts)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    // The transactions in remaining_accounts[start..start + limit] that pass every
+    // check execute_transaction applies, apart from who the executor is
+    pub fn get_executable_transactions(
+        ctx: Context<GetExecutableTransactions>,
+        start: u16,
+        limit: u16,
+    ) -> Result<Vec<Pubkey>> {
+        let wallet = &ctx.accounts.wallet;
+        let mut executable = Vec::new();
+
+        for info in ctx
+            .remaining_accounts
+            .iter()
+            .skip(start as usize)
+            .take(limit as usize)
+        {
+            if is_executable(info, wallet, ctx.remaining_accounts)? {
+                executable.push(info.key());
+            }
+        }
+
+        Ok(executable)
+    }
+
+    // The transactions in remaining_accounts[start..start + limit] that would be
+    // executable if not for the timelock, with the time each one unlocks
+    pub fn get_timelocked_transactions(
+        ctx: Context<GetTimelockedTransactions>,
+        start: u16,
+        limit: u16,
+    ) -> Result<Vec<TimelockedTransaction>> {
+        let wallet = &ctx.accounts.wallet;
+        let now = Clock::get()?.unix_timestamp;
+        let mut timelocked = Vec::new();
+
+        for info in ctx
+            .remaining_accounts
+            .iter()
+            .skip(start as usize)
+            .take(limit as usize)
+        {
+            if let Some(at) = executable_at(info, wallet, ctx.remaining_accounts)? {
+                if now < at {
+                    timelocked.push(TimelockedTransaction {
+                        transaction: info.key(),
+                        earliest_execution_at: at,
+                    });
+                }
+            }
+        }
+
+        Ok(timelocked)
+    }
+
+    // Number of executions within the last `window` seconds, counting at most the
+    // RECENT_EXECUTIONS_LEN most recent ones
+    pub fn recent_execution_rate(ctx: Context<RecentExecutionRate>, window: i64) -> Result<u8> {
+        let since = Clock::get()?.unix_timestamp.saturating_sub(window);
+        let count = ctx
+            .accounts
+            .wallet
+            .recent_executions
+            .iter()
+            .filter(|&&t| t != 0 && t > since)
+            .count();
+        Ok(count as u8)
+    }
+
+    // Keys that own this wallet directly and also own one of the nested owner wallets
+    // passed in remaining_accounts. Wallets that are not owners of this one are ignored.
+    pub fn detect_nested_double_counting(
+        ctx: Context<DetectNestedDoubleCounting>,
+    ) -> Result<Vec<Pubkey>> {
+        let wallet = &ctx.accounts.wallet;
+        let mut overlapping: Vec<Pubkey> = Vec::new();
+
+        for info in ctx.remaining_accounts.iter() {
+            if info.owner != &crate::ID {
+                continue;
+            }
+            let Ok(nested) = Wallet::try_deserialize(&mut &info.try_borrow_data()?[..]) else {
+                continue;
+            };
+
+            // A nested wallet holds weight here through its vault
+            let nested_vault = Pubkey::create_program_address(
+                &[VAULT_SEED, info.key.as_ref(), &[nested.nonce]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(ErrorCode::InvalidWallet))?;
+            if !wallet.is_owner(&nested_vault) {
+                continue;
+            }
+
+            for owner in nested.owners.iter() {
+                if wallet.is_owner(&owner.key) && !overlapping.contains(&owner.key) {
+                    overlapping.push(owner.key);
+                }
+            }
+        }
+
+        Ok(overlapping)
+    }
+
+    // With auto_execute on, the approval that reaches the threshold also executes the
+    // transaction when the execute accounts are passed as remaining accounts
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &mut ctx.accounts.transaction;
+        let signer = ctx.accounts.owner.key();
+
+        validate_approval(wallet, transaction, &signer)?;
+
+        add_signature(wallet, transaction, signer)?;
+
+        let may_execute = !wallet.proposer_must_execute || signer == transaction.creator;
+        if !wallet.auto_execute || !may_execute || ctx.remaining_accounts.is_empty() {
+            return Ok(());
+        }
+        if calculate_total_weight(wallet, &transaction.signers)? < wallet.threshold_weight
+            || !transaction.is_unlocked(Clock::get()?.unix_timestamp, wallet.execution_delay)
+        {
+            return Ok(());
+        }
+
+        let vault = Pubkey::create_program_address(
+            &[VAULT_SEED, wallet.key().as_ref(), &[wallet.nonce]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(ErrorCode::InvalidWallet))?;
+        execute_as_vault(
+            &mut ctx.accounts.wallet,
+            &mut ctx.accounts.transaction,
+            &vault,
+            signer,
+            ctx.remaining_accounts,
+        )
+    }
+
+    // Add the caller's signature and execute in one step. Fails, undoing the signature
+    // as well, when the transaction is still below the threshold.
+    pub fn approve_and_execute(ctx: Context<ApproveAndExecute>) -> Result<()> {
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &mut ctx.accounts.transaction;
+        let signer = ctx.accounts.owner.key();
+
+        validate_approval(wallet, transaction, &signer)?;
+        require!(
+            !wallet.proposer_must_execute || signer == transaction.creator,
+            ErrorCode::UnauthorizedExecutor
+        );
+
+        add_signature(wallet, transaction, signer)?;
+        validate_execution(wallet, transaction)?;
+
+        execute_as_vault(
+            &mut ctx.accounts.wallet,
+            &mut ctx.accounts.transaction,
+            &ctx.accounts.vault.key(),
+            signer,
+            ctx.remaining_accounts,
+        )
+    }
+
+    // Digest an owner signs off-chain to approve this exact transaction content
+    pub fn get_approval_digest(ctx: Context<GetApprovalDigest>) -> Result<[u8; 32]> {
+        let transaction = &ctx.accounts.transaction;
+        transaction.approval_digest(&transaction.key())
+    }
+
+    // Record an approval an owner signed off-chain, submitted by any relayer. The
+    // preceding instruction must be an ed25519 signature check over the approval digest.
+    pub fn verify_offchain_approval(
+        ctx: Context<VerifyOffchainApproval>,
+        owner: Pubkey,
+    ) -> Result<()> {
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &mut ctx.accounts.transaction;
+
+        validate_approval(wallet, transaction, &owner)?;
+
+        let digest = transaction.approval_digest(&transaction.key())?;
+        verify_ed25519_signature(&ctx.accounts.instructions, &owner, &digest)?;
+
+        add_signature(wallet, transaction, owner)?;
+
+        // The marker account cannot be created twice, so the signature cannot be replayed
+        let approval = &mut ctx.accounts.approval;
+        approval.transaction = transaction.key();
+        approval.owner = owner;
+        Ok(())
+    }
+
+    // Approve and leave a receipt PDA other programs can check
+    pub fn approve_with_receipt(ctx: Context<ApproveWithReceipt>) -> Result<()> {
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &mut ctx.accounts.transaction;
+        let signer = &ctx.accounts.owner;
+
+        validate_approval(wallet, transaction, &signer.key())?;
+
+        let weight = add_signature(wallet, transaction, signer.key())?;
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.transaction = transaction.key();
+        receipt.owner = signer.key();
+        receipt.weight = weight;
+        receipt.approved_at = Clock::get()?.unix_timestamp;
+        receipt.bump = ctx.bumps.receipt;
+        Ok(())
+    }
+
+    // Vote against a transaction; the owner must revoke before approving instead
+    pub fn reject_transaction(ctx: Context<RejectTransaction>) -> Result<()> {
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &mut ctx.accounts.transaction;
+        let signer = ctx.accounts.owner.key();
+
+        validate_approval(wallet, transaction, &signer)?;
+
+        transaction.rejections.push(signer);
+        transaction.rejection_weight = calculate_total_weight(wallet, &transaction.rejections)?;
+
+        // Cancel once the owners who have not rejected can no longer reach the threshold.
+        // Decided against the current owner set; later changes do not revive it.
+        let owners: Vec<Pubkey> = wallet.owners.iter().map(|o| o.key).collect();
+        let total_weight = calculate_total_weight(wallet, &owners)?;
+        if total_weight.saturating_sub(transaction.rejection_weight) < wallet.threshold_weight {
+            transaction.cancelled = true;
+            emit!(TransactionCancelled {
+                wallet: wallet.key(),
+                transaction: transaction.key(),
+                rejection_weight: transaction.rejection_weight,
+                owner_set_seqno: transaction.owner_set_seqno,
+            });
+        }
+        Ok(())
+    }
+
+    // Withdraw an approval before execution, closing its receipt if one exists, or
+    // withdraw a rejection
+    pub fn revoke_signature(ctx: Context<RevokeSignature>) -> Result<()> {
+        let wallet = &ctx.accounts.wallet;
+        let transaction = &mut ctx.accounts.transaction;
+        let signer = ctx.accounts.owner.key();
+
+        if let Some(position) = transaction.rejections.iter().position(|s| *s == signer) {
+            transaction.rejections.remove(position);
+            transaction.rejection_weight = calculate_total_weight(wallet, &transaction.rejections)?;
+            return Ok(());
+        }
+
+        let position = transaction
+            .signers
+            .iter()
+            .position(|s| *s == signer)
+            .ok_or(ErrorCode::NotSigned)?;
+        transaction.signers.remove(position);
+
+        // Dropping back below the threshold clears who made it executable
+        if calculate_total_weight(wallet, &transaction.signers)? < wallet.threshold_weight {
+            transaction.final_signer = None;
+        }
+
+        // A receipt must not outlive the approval it proves
+        let receipt = ctx.accounts.receipt.to_account_info();
+        if receipt.owner == &crate::ID {
+            let owner = ctx.accounts.owner.to_account_info();
+            let owner_lamports = owner.lamports();
+            **owner.try_borrow_mut_lamports()? = owner_lamports
+                .checked_add(receipt.lamports())
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            **receipt.try_borrow_mut_lamports()? = 0;
+            receipt.assign(&System::id());
+            receipt.realloc(0, false)?;
+        }
+        Ok(())
+    }
+
+    pub fn execute_transaction(ctx: Context<ExecuteTransaction>) -> Result<()> {
+        validate_execution(&ctx.accounts.wallet, &ctx.accounts.transaction)?;
+
+        execute_as_vault(
+            &mut ctx.accounts.wallet,
+            &mut ctx.accounts.transaction,
+            &ctx.accounts.vault.key(),
+            ctx.accounts.owner.key(),
+            ctx.remaining_accounts,
+        )
+    }
+
+    // Any owner may close an executed or cancelled transaction; the rent goes back to
+    // the creator, who paid it. A cancelled transaction is shrunk to a tombstone instead,
+    // since dependency_met reads a closed dependency as executed.
+    pub fn close_transaction(ctx: Context<CloseTransaction>) -> Result<()> {
+        let recipient = ctx.accounts.recipient.to_account_info();
+        let transaction = &mut ctx.accounts.transaction;
+
+        if transaction.executed {
+            msg!("Closing transaction account and returning rent to recipient");
+            return transaction.close(recipient);
+        }
+
+        transaction.instructions.clear();
+        transaction.signers.clear();
+        transaction.rejections.clear();
+        transaction.title.clear();
+        transaction.memo.clear();
+
+        let transaction_info = transaction.to_account_info();
+        let space = 8 + transaction.try_to_vec()?.len();
+        transaction_info.realloc(space, false)?;
+
+        // Keep only what the tombstone needs to stay rent exempt
+        let rent_exempt = Rent::get()?.minimum_balance(space);
+        let refund = transaction_info.lamports().saturating_sub(rent_exempt);
+        let transaction_lamports = transaction_info
+            .lamports()
+            .checked_sub(refund)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let recipient_lamports = recipient
+            .lamports()
+            .checked_add(refund)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        **transaction_info.try_borrow_mut_lamports()? = transaction_lamports;
+        **recipient.try_borrow_mut_lamports()? = recipient_lamports;
+
+        msg!(
+            "Shrunk cancelled transaction, returning {} lamports to recipient",
+            refund
+        );
+        Ok(())
+    }
+
+    // Return the rent of a transaction whose wallet was closed to its creator. Nothing
+    // can use the transaction any more, so anyone may close it in any state.
+    pub fn close_orphaned_transaction(_ctx: Context<CloseOrphanedTransaction>) -> Result<()> {
+        msg!("Closing orphaned transaction and returning rent to its creator");
+        Ok(())
+    }
+
+    // Sweep lamports sent to an executed or cancelled transaction account back into the
+    // vault; only callable through an approved proposal
+    pub fn reclaim_stray_funds(ctx: Context<ReclaimStrayFunds>) -> Result<()> {
+        let transaction_info = ctx.accounts.transaction.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+
+        // Keep the transaction account rent exempt until it is closed
+        let rent_exempt = Rent::get()?.minimum_balance(transaction_info.data_len());
+        let stray = transaction_info.lamports().saturating_sub(rent_exempt);
+        require!(stray > 0, ErrorCode::NoStrayFunds);
+
+        let transaction_lamports = transaction_info
+            .lamports()
+            .checked_sub(stray)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let vault_lamports = vault_info
+            .lamports()
+            .checked_add(stray)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        **transaction_info.try_borrow_mut_lamports()? = transaction_lamports;
+        **vault_info.try_borrow_mut_lamports()? = vault_lamports;
+
+        msg!("Reclaimed {} lamports into the vault", stray);
+        Ok(())
+    }
+
+    // Grow the owner set; only callable through an approved proposal
+    pub fn add_owner(ctx: Context<AddOwner>, new_owner: OwnerConfig) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+
+        require!(
+            !wallet.is_owner(&new_owner.key),
+            ErrorCode::OwnerAlreadyExists
+        );
+        require!(new_owner.weight > 0, ErrorCode::InvalidOwnerWeight);
+        require!(wallet.owners.len() < MAX_SIGNERS, ErrorCode::TooManyOwners);
+        validate_owner_labels(std::slice::from_ref(&new_owner))?;
+        validate_owner_expiries(std::slice::from_ref(&new_owner))?;
+
+        msg!("Owner added: {}", new_owner.key);
+        wallet.owners.push(new_owner);
+        sum_owner_weights(&wallet.owners)?;
+        wallet.increment_owner_set_seqno()?;
+
+        Ok(())
+    }
+
+    // Rewrite a wallet created with the legacy layout into the current one, growing the
+    // account for the new fields. Settings added since keep their defaults and
+    // created_at stays 0, since the creation time is unknown.
+    pub fn migrate_wallet(ctx: Context<MigrateWallet>) -> Result<()> {
+        let wallet_info = ctx.accounts.wallet.to_account_info();
+        let wallet = LegacyWallet::load(&wallet_info.try_borrow_data()?)?.upgrade();
+        require!(
+            wallet.is_owner(&ctx.accounts.owner.key()),
+            ErrorCode::NotOwner
+        );
+
+        let space = Wallet::space(wallet.owners.len());
+        let rent_exempt = Rent::get()?.minimum_balance(space);
+        let lamports = wallet_info.lamports();
+        if rent_exempt > lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: wallet_info.clone(),
+                    },
+                ),
+                rent_exempt - lamports,
+            )?;
+        }
+
+        wallet_info.realloc(space, true)?;
+        wallet.try_serialize(&mut &mut wallet_info.try_borrow_mut_data()?[..])?;
+
+        msg!("Wallet migrated with {} owners", wallet.owners.len());
+        Ok(())
+    }
+
+    // Shrink the owner set; only callable through an approved proposal
+    pub fn remove_owner(
+        ctx: Context<RemoveOwner>,
+        owner: Pubkey,
+        cancel_proposals: bool,
+    ) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+
+        let position = wallet
+            .owners
+            .iter()
+            .position(|o| o.key == owner)
+            .ok_or(ErrorCode::OwnerNotFound)?;
+        wallet.owners.remove(position);
+        require!(!wallet.owners.is_empty(), ErrorCode::NoOwners);
+        require!(
+            wallet.owners.len() >= wallet.min_owners as usize,
+            ErrorCode::TooFewOwners
+        );
+
+        // The remaining owners must still be able to reach the threshold
+        require!(
+            wallet.threshold_weight <= active_owner_weight(&wallet.owners)?,
+            ErrorCode::ThresholdTooHigh
+        );
+        wallet.increment_owner_set_seqno()?;
+
+        // Optionally cancel the removed owner's pending transactions passed in
+        // remaining_accounts, so they can be closed; anything else is left as is
+        if cancel_proposals {
+            for info in ctx.remaining_accounts.iter() {
+                if info.owner != &crate::ID {
+                    continue;
+                }
+                let Ok(mut transaction) =
+                    Transaction::try_deserialize(&mut &info.try_borrow_data()?[..])
+                else {
+                    continue;
+                };
+                if transaction.wallet != wallet.key()
+                    || transaction.creator != owner
+                    || transaction.executed
+                    || transaction.cancelled
+                {
+                    continue;
+                }
+
+                transaction.cancelled = true;
+                transaction.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+                emit!(TransactionCancelled {
+                    wallet: wallet.key(),
+                    transaction: info.key(),
+                    rejection_weight: transaction.rejection_weight,
+                    owner_set_seqno: transaction.owner_set_seqno,
+                });
+            }
+        }
+
+        let space = Wallet::space(wallet.owners.len());
+        realloc_wallet(
+            &ctx.accounts.wallet,
+            &ctx.accounts.vault,
+            &ctx.accounts.system_program,
+            space,
+        )?;
+
+        msg!("Owner removed: {}", owner);
+        Ok(())
+    }
+
+    // Replace the whole owner set and threshold at once; only callable through an
+    // approved proposal. Pending transactions must be approved again by the new owners.
+    pub fn set_owners(
+        ctx: Context<SetOwners>,
+        owners: Vec<OwnerConfig>,
+        threshold_weight: u64,
+    ) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+
+        validate_owner_expiries(&owners)?;
+        validate_owners(&owners, threshold_weight, wallet.allow_zero_threshold)?;
+        validate_owner_labels(&owners)?;
+        require!(
+            owners.len() >= wallet.min_owners as usize,
+            ErrorCode::TooFewOwners
+        );
+
+        wallet.owners = owners;
+        wallet.threshold_weight = threshold_weight;
+        wallet.increment_owner_set_seqno()?;
+
+        let space = Wallet::space(wallet.owners.len());
+        realloc_wallet(
+            &ctx.accounts.wallet,
+            &ctx.accounts.vault,
+            &ctx.accounts.system_program,
+            space,
+        )
+    }
+
+    // Rotate an owner's key while keeping its weight; only callable through an approved
+    // proposal. The seqno bump drops approvals made with the old key.
+    pub fn replace_owner(
+        ctx: Context<ReplaceOwner>,
+        old_owner: Pubkey,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        require!(!wallet.is_owner(&new_owner), ErrorCode::OwnerAlreadyExists);
+
+        let owner = wallet
+            .owners
+            .iter_mut()
+            .find(|o| o.key == old_owner)
+            .ok_or(ErrorCode::OwnerNotFound)?;
+        owner.key = new_owner;
+        wallet.increment_owner_set_seqno()?;
+
+        msg!("Owner replaced: {} -> {}", old_owner, new_owner);
+        Ok(())
+    }
+
+    // First step of a key rotation; only callable through an approved proposal. The old
+    // key keeps working until the new key accepts.
+    pub fn propose_key_rotation(
+        ctx: Context<ProposeKeyRotation>,
+        old_owner: Pubkey,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        require!(
+            wallet.pending_key_rotation.is_none(),
+            ErrorCode::KeyRotationPending
+        );
+        require!(wallet.is_owner(&old_owner), ErrorCode::OwnerNotFound);
+        require!(!wallet.is_owner(&new_owner), ErrorCode::OwnerAlreadyExists);
+
+        wallet.pending_key_rotation = Some(KeyRotation {
+            old_owner,
+            new_owner,
+        });
+        msg!("Key rotation proposed: {} -> {}", old_owner, new_owner);
+        Ok(())
+    }
+
+    // Second step: the new key proves it can sign, then takes over the seat
+    pub fn accept_key_rotation(ctx: Context<AcceptKeyRotation>) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        let rotation = wallet
+            .pending_key_rotation
+            .take()
+            .ok_or(ErrorCode::NoKeyRotation)?;
+        require_keys_eq!(
+            rotation.new_owner,
+            ctx.accounts.new_owner.key(),
+            ErrorCode::UnauthorizedKeyRotation
+        );
+
+        // The owner set may have changed since the rotation was proposed
+        require!(
+            !wallet.is_owner(&rotation.new_owner),
+            ErrorCode::OwnerAlreadyExists
+        );
+        let owner = wallet
+            .owners
+            .iter_mut()
+            .find(|o| o.key == rotation.old_owner)
+            .ok_or(ErrorCode::OwnerNotFound)?;
+        owner.key = rotation.new_owner;
+        wallet.increment_owner_set_seqno()?;
+
+        msg!(
+            "Key rotation accepted: {} -> {}",
+            rotation.old_owner,
+            rotation.new_owner
+        );
+        Ok(())
+    }
+
+    // Drop a pending rotation; callable by the old key or through an approved proposal
+    pub fn cancel_key_rotation(ctx: Context<CancelKeyRotation>) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let wallet = &mut ctx.accounts.wallet;
+        let rotation = wallet
+            .pending_key_rotation
+            .as_ref()
+            .ok_or(ErrorCode::NoKeyRotation)?;
+        require!(
+            authority == rotation.old_owner || authority == ctx.accounts.vault.key(),
+            ErrorCode::UnauthorizedKeyRotation
+        );
+
+        wallet.pending_key_rotation = None;
+        msg!("Key rotation cancelled");
+        Ok(())
+    }
+
+    // Neutralize a compromised key by zeroing its weight while keeping it listed;
+    // only callable through an approved proposal
+    pub fn quarantine_owner(ctx: Context<QuarantineOwner>, owner: Pubkey) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        require!(!wallet.is_quarantined(&owner), ErrorCode::OwnerQuarantined);
+
+        let entry = wallet
+            .owners
+            .iter_mut()
+            .find(|o| o.key == owner)
+            .ok_or(ErrorCode::OwnerNotFound)?;
+        entry.weight = 0;
+
+        // The remaining owners must still be able to reach the threshold
+        require!(
+            wallet.threshold_weight <= active_owner_weight(&wallet.owners)?,
+            ErrorCode::ThresholdTooHigh
+        );
+        wallet.increment_owner_set_seqno()?;
+
+        msg!("Owner quarantined: {}", owner);
+        Ok(())
+    }
+
+    // Record the current owners and threshold in a new archive account that is never modified
+    pub fn archive_state(ctx: Context<ArchiveState>) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        let archive = &mut ctx.accounts.archive;
+
+        archive.wallet = wallet.key();
+        archive.index = wallet.archive_count;
+        archive.owners = wallet.owners.clone();
+        archive.threshold_weight = wallet.threshold_weight;
+        archive.owner_set_seqno = wallet.owner_set_seqno;
+        archive.archived_at = Clock::get()?.unix_timestamp;
+
+        wallet.archive_count = wallet
+            .archive_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Retire the wallet: empty the vault and close the wallet account. Only callable
+    // through an approved proposal. Transactions still referencing the wallet can no
+    // longer be approved or executed, only closed through close_orphaned_transaction.
+    // A ClosedWallet marker stays behind so the address cannot be created again with a
+    // fresh owner_set_seqno.
+    pub fn close_wallet(ctx: Context<CloseWallet>) -> Result<()> {
+        check_allowlist(
+            &ctx.accounts.wallet,
+            &[ctx.accounts.destination.key()],
+            ctx.remaining_accounts,
+        )?;
+
+        let closed_wallet = &mut ctx.accounts.closed_wallet;
+        closed_wallet.wallet = ctx.accounts.wallet.key();
+        closed_wallet.closed_at = Clock::get()?.unix_timestamp;
+
+        let balance = ctx.accounts.vault.lamports();
+        if balance > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.destination.to_account_info(),
+                    },
+                ),
+                balance,
+            )?;
+        }
+        msg!("Wallet closed, {} lamports moved out of the vault", balance);
+        Ok(())
+    }
+
+    // Let an owner name their own entry; labels carry no weight and need no proposal
+    pub fn set_owner_label(
+        ctx: Context<SetOwnerLabel>,
+        owner: Pubkey,
+        label: String,
+    ) -> Result<()> {
+        require_keys_eq!(owner, ctx.accounts.owner.key(), ErrorCode::NotOwner);
+        require!(label.len() <= MAX_LABEL_LEN, ErrorCode::InvalidNameLength);
+
+        let entry = ctx
+            .accounts
+            .wallet
+            .owners
+            .iter_mut()
+            .find(|o| o.key == owner)
+            .ok_or(ErrorCode::NotOwner)?;
+        // Like any other action, an expired owner can no longer do this
+        require!(
+            !entry.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::OwnerNotFound
+        );
+        entry.label = label;
+        Ok(())
+    }
+
+    // Approve a payment schedule once; only callable through an approved proposal
+    pub fn create_recurring_payment(
+        ctx: Context<CreateRecurringPayment>,
+        _id: u64,
+        config: RecurringPaymentConfig,
+    ) -> Result<()> {
+        require!(
+            config.amount > 0
+                && config.interval > 0
+                && config
+                    .end_time
+                    .map_or(true, |end_time| end_time >= config.start_time)
+                && config.max_executions.map_or(true, |max| max > 0),
+            ErrorCode::InvalidRecurringPayment
+        );
+
+        let recurring_payment = &mut ctx.accounts.recurring_payment;
+        recurring_payment.wallet = ctx.accounts.wallet.key();
+        recurring_payment.destination = config.destination;
+        recurring_payment.amount = config.amount;
+        recurring_payment.interval = config.interval;
+        recurring_payment.start_time = config.start_time;
+        recurring_payment.end_time = config.end_time;
+        recurring_payment.max_executions = config.max_executions;
+        recurring_payment.executions_count = 0;
+        recurring_payment.last_executed = 0;
+        recurring_payment.cancelled = false;
+        Ok(())
+    }
+
+    // Pay the next installment of a schedule; anyone can call it once it is due
+    pub fn execute_recurring_payment(ctx: Context<ExecuteRecurringPayment>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let recurring_payment = &mut ctx.accounts.recurring_payment;
+        require!(
+            !recurring_payment.is_finished(now),
+            ErrorCode::RecurringPaymentFinished
+        );
+        require!(
+            now >= recurring_payment.next_due()?,
+            ErrorCode::RecurringPaymentNotDue
+        );
+        check_allowlist(
+            &ctx.accounts.wallet,
+            &[recurring_payment.destination],
+            ctx.remaining_accounts,
+        )?;
+
+        transfer_from_vault(
+            &ctx.accounts.wallet,
+            &ctx.accounts.vault,
+            &ctx.accounts.destination,
+            &ctx.accounts.system_program,
+            recurring_payment.amount,
+        )?;
+
+        recurring_payment.last_executed = now;
+        recurring_payment.executions_count = recurring_payment
+            .executions_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Stop a schedule; only callable through an approved proposal
+    pub fn cancel_recurring_payment(ctx: Context<CancelRecurringPayment>) -> Result<()> {
+        ctx.accounts.recurring_payment.cancelled = true;
+        Ok(())
+    }
 
-        let wallet = &ctx.accounts.wallet;
-        let owner = &ctx.accounts.owner;
-        require!(wallet.is_owner(&owner.key()), ErrorCode::NotOwner);
+    // Return the rent of a cancelled or finished schedule to the vault
+    pub fn close_recurring_payment(ctx: Context<CloseRecurringPayment>) -> Result<()> {
+        require!(
+            ctx.accounts
+                .recurring_payment
+                .is_finished(Clock::get()?.unix_timestamp),
+            ErrorCode::RecurringPaymentActive
+        );
+        Ok(())
+    }
 
-        let transaction = &mut ctx.accounts.transaction;
-        transaction.initialize(
-            instructions,
-            wallet.key(),
-            owner.key(),
-            wallet.owner_set_seqno,
+    // Give an owner a per-period allowance; only callable through an approved proposal
+    pub fn create_spending_limit(
+        ctx: Context<CreateSpendingLimit>,
+        owner: Pubkey,
+        amount: u64,
+        period: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.wallet.is_owner(&owner),
+            ErrorCode::OwnerNotFound
         );
+        require!(amount > 0 && period > 0, ErrorCode::InvalidSpendingLimit);
 
+        let spending_limit = &mut ctx.accounts.spending_limit;
+        spending_limit.wallet = ctx.accounts.wallet.key();
+        spending_limit.owner = owner;
+        spending_limit.amount = amount;
+        spending_limit.period = period;
+        spending_limit.remaining = amount;
+        spending_limit.last_reset = Clock::get()?.unix_timestamp;
         Ok(())
     }
 
-    pub fn approve(ctx: Context<Approve>) -> Result<()> {
-        let wallet = &ctx.accounts.wallet;
-        let transaction = &mut ctx.accounts.transaction;
-        let signer = &ctx.accounts.owner;
+    // Change an allowance and restart its period; only callable through an approved proposal
+    pub fn update_spending_limit(
+        ctx: Context<UpdateSpendingLimit>,
+        amount: u64,
+        period: i64,
+    ) -> Result<()> {
+        require!(amount > 0 && period > 0, ErrorCode::InvalidSpendingLimit);
 
-        validate_approval(wallet, transaction, signer)?;
+        let spending_limit = &mut ctx.accounts.spending_limit;
+        spending_limit.amount = amount;
+        spending_limit.period = period;
+        spending_limit.remaining = amount;
+        spending_limit.last_reset = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
 
-        transaction.signers.push(signer.key());
+    // Revoke an allowance; only callable through an approved proposal
+    pub fn remove_spending_limit(_ctx: Context<RemoveSpendingLimit>) -> Result<()> {
         Ok(())
     }
 
-    pub fn execute_transaction(ctx: Context<ExecuteTransaction>) -> Result<()> {
+    // Move lamports from the vault within the caller's allowance, without a proposal
+    pub fn spend_from_allowance(ctx: Context<SpendFromAllowance>, amount: u64) -> Result<()> {
         let wallet = &ctx.accounts.wallet;
-        let transaction = &mut ctx.accounts.transaction;
-        let vault = &ctx.accounts.vault;
+        let owner = ctx.accounts.owner.key();
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            wallet.is_owner(&owner) && !wallet.is_expired(&owner, now),
+            ErrorCode::NotOwner
+        );
+        require!(!wallet.is_quarantined(&owner), ErrorCode::OwnerQuarantined);
+        check_allowlist(
+            wallet,
+            &[ctx.accounts.destination.key()],
+            ctx.remaining_accounts,
+        )?;
 
-        validate_execution(wallet, transaction)?;
+        let spending_limit = &mut ctx.accounts.spending_limit;
+        spending_limit.refresh(now);
+        spending_limit.remaining = spending_limit
+            .remaining
+            .checked_sub(amount)
+            .ok_or(ErrorCode::SpendingLimitExceeded)?;
 
-        // Prepare PDA signer seeds
-        let seeds = &[
-            VAULT_SEED,
-            wallet.to_account_info().key.as_ref(),
-            &[wallet.nonce],
-        ];
-        let signer_seeds = &[&seeds[..]];
-
-        // Execute each instruction in the transaction
-        for (i, instruction) in transaction.instructions.iter().enumerate() {
-            msg!("Processing instruction {}", i);
-
-            // Find vault's position in accounts list
-            let vault_index = instruction
-                .accounts
-                .iter()
-                .position(|acc| acc.pubkey == vault.key())
-                .ok_or(ErrorCode::AccountNotFound)?;
+        transfer_from_vault(
+            wallet,
+            &ctx.accounts.vault,
+            &ctx.accounts.destination,
+            &ctx.accounts.system_program,
+            amount,
+        )
+    }
 
-            // Prepare account metas with vault as signer
-            let accounts_metas: Vec<AccountMeta> = instruction
-                .accounts
-                .iter()
-                .enumerate()
-                .map(|(idx, acc)| {
-                    if idx == vault_index {
-                        AccountMeta::new(acc.pubkey, true)
-                    } else {
-                        acc.to_account_meta()
-                    }
-                })
-                .collect();
+    // Only callable through an approved proposal
+    pub fn create_destination_allowlist(ctx: Context<CreateDestinationAllowlist>) -> Result<()> {
+        let allowlist = &mut ctx.accounts.allowlist;
+        allowlist.wallet = ctx.accounts.wallet.key();
+        allowlist.destinations = Vec::new();
+        Ok(())
+    }
 
-            let ix = Instruction {
-                program_id: instruction.program_id,
-                accounts: accounts_metas,
-                data: instruction.data.clone(),
-            };
+    // Only callable through an approved proposal
+    pub fn add_allowed_destination(
+        ctx: Context<UpdateDestinationAllowlist>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        let allowlist = &mut ctx.accounts.allowlist;
+        require!(
+            !allowlist.destinations.contains(&destination),
+            ErrorCode::DuplicateDestination
+        );
+        require!(
+            allowlist.destinations.len() < MAX_ALLOWED_DESTINATIONS,
+            ErrorCode::AllowlistFull
+        );
+        allowlist.destinations.push(destination);
+        Ok(())
+    }
 
-            // Execute CPI call
-            invoke_signed(&ix, ctx.remaining_accounts, signer_seeds)
-                .map_err(|_| error!(ErrorCode::TransactionExecutionFailed))?;
+    // Only callable through an approved proposal. Pending transactions paying the
+    // removed destination can no longer execute while the allowlist is enforced.
+    pub fn remove_allowed_destination(
+        ctx: Context<UpdateDestinationAllowlist>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        let allowlist = &mut ctx.accounts.allowlist;
+        let index = allowlist
+            .destinations
+            .iter()
+            .position(|d| *d == destination)
+            .ok_or(ErrorCode::DestinationNotAllowed)?;
+        allowlist.destinations.remove(index);
+        Ok(())
+    }
 
-            msg!("Instruction {} executed successfully", i);
-        }
+    // Stop accepting new proposals while letting pending ones finish; only callable
+    // through an approved proposal
+    pub fn set_proposals_paused(ctx: Context<SetProposalsPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.wallet.proposals_paused = paused;
+        msg!("Proposals paused: {}", paused);
+        Ok(())
+    }
+
+    // Execute a transaction inside the approval that makes it executable; only callable
+    // through an approved proposal
+    pub fn set_auto_execute(ctx: Context<SetAutoExecute>, enabled: bool) -> Result<()> {
+        ctx.accounts.wallet.auto_execute = enabled;
+        msg!("Auto execute: {}", enabled);
+        Ok(())
+    }
+
+    // Restrict vault payouts to the destination allowlist; only callable through an
+    // approved proposal
+    pub fn set_enforce_allowlist(ctx: Context<SetEnforceAllowlist>, enabled: bool) -> Result<()> {
+        ctx.accounts.wallet.enforce_allowlist = enabled;
+        msg!("Enforce allowlist: {}", enabled);
+        Ok(())
+    }
+
+    // Only callable through an approved proposal
+    pub fn add_allowed_program(
+        ctx: Context<UpdateAllowedPrograms>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        require!(
+            !wallet.allowed_programs.contains(&program_id),
+            ErrorCode::DuplicateProgram
+        );
+        require!(
+            wallet.allowed_programs.len() < MAX_ALLOWED_PROGRAMS,
+            ErrorCode::AllowlistFull
+        );
+        wallet.allowed_programs.push(program_id);
+        Ok(())
+    }
 
-        transaction.executed = true;
+    // Only callable through an approved proposal. Removing the last entry lifts the
+    // restriction.
+    pub fn remove_allowed_program(
+        ctx: Context<UpdateAllowedPrograms>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        let index = wallet
+            .allowed_programs
+            .iter()
+            .position(|p| *p == program_id)
+            .ok_or(ErrorCode::ProgramNotAllowed)?;
+        wallet.allowed_programs.remove(index);
+        Ok(())
+    }
+
+    // Minimum time between creating and executing any transaction; only callable
+    // through an approved proposal
+    pub fn set_execution_delay(ctx: Context<SetExecutionDelay>, delay: i64) -> Result<()> {
+        require!(delay >= 0, ErrorCode::InvalidExecutionDelay);
+        ctx.accounts.wallet.execution_delay = delay;
+        msg!("Execution delay: {}", delay);
+        Ok(())
+    }
+
+    // Cap how many transactions the wallet may ever propose, counting those already
+    // proposed; 0 removes the cap. Only callable through an approved proposal.
+    pub fn set_max_transactions(
+        ctx: Context<SetMaxTransactions>,
+        max_transactions: u64,
+    ) -> Result<()> {
+        ctx.accounts.wallet.max_transactions = max_transactions;
+        msg!("Max transactions: {}", max_transactions);
         Ok(())
     }
 
-    pub fn close_transaction(_ctx: Context<CloseTransaction>) -> Result<()> {
-        // Close account and return rent
-        msg!("Closing transaction account and returning rent to recipient");
+    // Require the creator of a transaction to be the one who executes it; only callable
+    // through an approved proposal
+    pub fn set_proposer_must_execute(
+        ctx: Context<SetProposerMustExecute>,
+        enabled: bool,
+    ) -> Result<()> {
+        ctx.accounts.wallet.proposer_must_execute = enabled;
+        msg!("Proposer must execute: {}", enabled);
         Ok(())
     }
 
-    // Modify threshold weight for the wallet
+    // Modify threshold weight for the wallet; only callable through an approved proposal.
+    // The seqno bump invalidates transactions approved under the old threshold.
     pub fn change_threshold(ctx: Context<ChangeThreshold>, new_threshold: u64) -> Result<()> {
         let wallet = &mut ctx.accounts.wallet;
-        let total_weight: u64 = wallet.owners.iter().map(|owner| owner.weight).sum();
+        let total_weight = active_owner_weight(&wallet.owners)?;
 
         // Validate new threshold
-        require!(new_threshold > 0, ErrorCode::InvalidThreshold);
+        require!(
+            new_threshold > 0 || wallet.allow_zero_threshold,
+            ErrorCode::InvalidThreshold
+        );
         require!(new_threshold <= total_weight, ErrorCode::ThresholdTooHigh);
 
         // Update threshold and increment sequence number
+        let old_threshold = wallet.threshold_weight;
         wallet.threshold_weight = new_threshold;
-        wallet.owner_set_seqno += 1;
+        wallet.increment_owner_set_seqno()?;
+
+        emit!(ThresholdChanged {
+            wallet: wallet.key(),
+            old_threshold,
+            new_threshold,
+            owner_set_seqno: wallet.owner_set_seqno,
+        });
 
         Ok(())
     }
 
-    // Modify weight for a single owner
+    // Modify weight for a single owner; only callable through an approved proposal.
+    // The seqno bump invalidates transactions approved under the old weights.
     pub fn change_owner_weight(
         ctx: Context<ChangeOwnerWeight>,
         owner_key: Pubkey,
@@ -163,7 +1354,7 @@ pub mod multisig_wallet {
             owner.weight = new_weight;
 
             // Calculate new total weight
-            let total_weight: u64 = wallet.owners.iter().map(|o| o.weight).sum();
+            let total_weight = active_owner_weight(&wallet.owners)?;
 
             // Ensure threshold remains valid
             require!(
@@ -171,7 +1362,7 @@ pub mod multisig_wallet {
                 ErrorCode::ThresholdTooHigh
             );
 
-            wallet.owner_set_seqno += 1;
+            wallet.increment_owner_set_seqno()?;
         } else {
             return err!(ErrorCode::OwnerNotFound);
         }
@@ -179,10 +1370,10 @@ pub mod multisig_wallet {
         Ok(())
     }
 
-    // Update entire weight configuration
+    // Update entire weight configuration; only callable through an approved proposal
     pub fn change_owner_weights(
         ctx: Context<ChangeOwnerWeights>,
-        new_weights: Vec<OwnerConfig>,
+        new_weights: Vec<WeightChange>,
     ) -> Result<()> {
         let wallet = &mut ctx.accounts.wallet;
 
@@ -191,37 +1382,54 @@ pub mod multisig_wallet {
             wallet.owners.len() == new_weights.len(),
             ErrorCode::InvalidOwnerCount
         );
-
-        // Validate new weight configuration
-        for new_config in new_weights.iter() {
-            require!(
-                wallet.owners.iter().any(|o| o.key == new_config.key),
-                ErrorCode::OwnerNotFound
-            );
-            require!(new_config.weight > 0, ErrorCode::InvalidOwnerWeight);
-        }
+        apply_weight_changes(&mut wallet.owners, &new_weights)?;
 
         // Calculate new total weight
-        let new_total_weight: u64 = new_weights.iter().map(|o| o.weight).sum();
+        let new_total_weight = active_owner_weight(&wallet.owners)?;
         require!(
             wallet.threshold_weight <= new_total_weight,
             ErrorCode::ThresholdTooHigh
         );
 
-        // Update weights and increment sequence
-        wallet.owners = new_weights;
-        wallet.owner_set_seqno += 1;
+        wallet.increment_owner_set_seqno()?;
+
+        Ok(())
+    }
+
+    // Apply weight changes to a subset of owners as a single update; only callable
+    // through an approved proposal
+    pub fn batch_change_weights(
+        ctx: Context<BatchChangeWeights>,
+        changes: Vec<WeightChange>,
+    ) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+
+        apply_weight_changes(&mut wallet.owners, &changes)?;
+
+        // Only the final configuration has to satisfy the threshold
+        let total_weight = active_owner_weight(&wallet.owners)?;
+        require!(
+            wallet.threshold_weight <= total_weight,
+            ErrorCode::ThresholdTooHigh
+        );
+
+        wallet.increment_owner_set_seqno()?;
 
         Ok(())
     }
 }
 
-// Calculate total signing weight
-fn calculate_total_weight(wallet: &Account<Wallet>, signers: &[Pubkey]) -> Result<u64> {
+// Calculate total signing weight; signatures of expired owners no longer count
+fn calculate_total_weight(wallet: &Wallet, signers: &[Pubkey]) -> Result<u64> {
+    let now = Clock::get()?.unix_timestamp;
     let mut total_weight = 0u64;
 
     for signer in signers.iter() {
-        if let Some(owner) = wallet.owners.iter().find(|o| o.key == *signer) {
+        if let Some(owner) = wallet
+            .owners
+            .iter()
+            .find(|o| o.key == *signer && !o.is_expired(now))
+        {
             total_weight = total_weight
                 .checked_add(owner.weight)
                 .ok_or(ErrorCode::ArithmeticOverflow)?;
@@ -231,13 +1439,337 @@ fn calculate_total_weight(wallet: &Account<Wallet>, signers: &[Pubkey]) -> Resul
     Ok(total_weight)
 }
 
+// Record a signature and, if it crosses the threshold, who made it executable.
+// Returns the weight the signer contributed.
+fn add_signature(wallet: &Wallet, transaction: &mut Transaction, signer: Pubkey) -> Result<u64> {
+    let weight_before = calculate_total_weight(wallet, &transaction.signers)?;
+    transaction.signers.push(signer);
+    let weight_after = calculate_total_weight(wallet, &transaction.signers)?;
+
+    if weight_before < wallet.threshold_weight && weight_after >= wallet.threshold_weight {
+        transaction.final_signer = Some(signer);
+    }
+    Ok(weight_after - weight_before)
+}
+
+// Whether `info` is a transaction of this wallet that could be executed now.
+// Accounts that are not transactions, and unresolvable dependencies, count as not executable.
+fn is_executable(
+    info: &AccountInfo,
+    wallet: &Account<Wallet>,
+    remaining: &[AccountInfo],
+) -> Result<bool> {
+    let now = Clock::get()?.unix_timestamp;
+    Ok(executable_at(info, wallet, remaining)?.is_some_and(|at| now >= at))
+}
+
+// When `info` becomes executable, if every check execute_transaction applies other
+// than the timelock already passes
+fn executable_at(
+    info: &AccountInfo,
+    wallet: &Account<Wallet>,
+    remaining: &[AccountInfo],
+) -> Result<Option<i64>> {
+    if info.owner != &crate::ID {
+        return Ok(None);
+    }
+    let Ok(transaction) = Transaction::try_deserialize(&mut &info.try_borrow_data()?[..]) else {
+        return Ok(None);
+    };
+
+    if transaction.wallet != wallet.key()
+        || transaction.executed
+        || transaction.cancelled
+        || transaction.owner_set_seqno != wallet.owner_set_seqno
+        || calculate_total_weight(wallet, &transaction.signers)? < wallet.threshold_weight
+    {
+        return Ok(None);
+    }
+    // execute_as_vault re-checks both allowlists against their current contents; the
+    // destination allowlist is looked up among the remaining accounts
+    if validate_programs(wallet, &transaction.instructions).is_err()
+        || validate_destinations(wallet, &transaction.instructions, remaining).is_err()
+    {
+        return Ok(None);
+    }
+    if let Some(dependency) = transaction.depends_on {
+        if !dependency_met(&dependency, &wallet.key(), remaining).unwrap_or(false) {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(
+        transaction.earliest_execution_at(wallet.execution_delay),
+    ))
+}
+
+// Check that the instruction before the current one is an ed25519 program call
+// verifying `signer`'s signature over `message`, with all data inline
+fn verify_ed25519_signature(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, ErrorCode::InvalidOffchainApproval);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        ErrorCode::InvalidOffchainApproval
+    );
+
+    // One signature: count, padding, then the 14-byte offsets struct
+    let data = &ix.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        ErrorCode::InvalidOffchainApproval
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+
+    // The signature, key and message must all come from the ed25519 instruction itself
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        ErrorCode::InvalidOffchainApproval
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidOffchainApproval)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidOffchainApproval)?;
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        ErrorCode::InvalidOffchainApproval
+    );
+    Ok(())
+}
+
+// Check the dependency, run every instruction of the transaction as the vault and
+// record the execution
+fn execute_as_vault(
+    wallet: &mut Account<Wallet>,
+    transaction: &mut Account<Transaction>,
+    vault: &Pubkey,
+    executor: Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    require!(!transaction.cancelled, ErrorCode::TransactionCancelled);
+    require!(
+        transaction.is_unlocked(Clock::get()?.unix_timestamp, wallet.execution_delay),
+        ErrorCode::TimelockNotExpired
+    );
+    if let Some(dependency) = transaction.depends_on {
+        require!(
+            dependency_met(&dependency, &wallet.key(), remaining_accounts)?,
+            ErrorCode::DependencyNotMet
+        );
+    }
+    // The allowlists may have changed since the transaction was proposed
+    validate_programs(wallet, &transaction.instructions)?;
+    validate_destinations(wallet, &transaction.instructions, remaining_accounts)?;
+
+    // Prepare PDA signer seeds
+    let wallet_key = wallet.key();
+    let seeds = &[VAULT_SEED, wallet_key.as_ref(), &[wallet.nonce]];
+    let signer_seeds = &[&seeds[..]];
+
+    // Execute each instruction in the transaction
+    for (i, instruction) in transaction.instructions.iter().enumerate() {
+        msg!("Processing instruction {}", i);
+
+        let ix = to_vault_instruction(instruction, vault)?;
+
+        // Execute CPI call
+        invoke_signed(&ix, remaining_accounts, signer_seeds)
+            .map_err(|_| error!(ErrorCode::TransactionExecutionFailed))?;
+
+        msg!("Instruction {} executed successfully", i);
+    }
+
+    transaction.executed = true;
+
+    // Instructions calling back into this program may have rewritten the wallet, or
+    // closed it through close_wallet
+    if wallet.to_account_info().owner == &crate::ID {
+        wallet.reload()?;
+        wallet.record_execution(Clock::get()?.unix_timestamp);
+    }
+
+    emit!(TransactionExecuted {
+        wallet: wallet_key,
+        transaction: transaction.key(),
+        executor,
+        final_signer: transaction.final_signer,
+        owner_set_seqno: transaction.owner_set_seqno,
+    });
+
+    Ok(())
+}
+
+// Resize the wallet account after its data changed, with the vault paying or receiving
+// the rent difference
+fn realloc_wallet<'info>(
+    wallet: &Account<'info, Wallet>,
+    vault: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+) -> Result<()> {
+    let wallet_info = wallet.to_account_info();
+    check_shrink(&**wallet, wallet_info.data_len(), space)?;
+
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+    let lamports = wallet_info.lamports();
+    if rent_exempt > lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: vault.to_account_info(),
+                    to: wallet_info.clone(),
+                },
+            ),
+            rent_exempt - lamports,
+        )?;
+    } else if lamports > rent_exempt {
+        let vault_info = vault.to_account_info();
+        let vault_lamports = vault_info
+            .lamports()
+            .checked_add(lamports - rent_exempt)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        **wallet_info.try_borrow_mut_lamports()? = rent_exempt;
+        **vault_info.try_borrow_mut_lamports()? = vault_lamports;
+    }
+
+    wallet_info.realloc(space, false)?;
+    Ok(())
+}
+
+// A size formula that undercounts must fail here instead of truncating the account
+fn check_shrink<T: AnchorSerialize>(data: &T, current_len: usize, new_len: usize) -> Result<()> {
+    if new_len < current_len {
+        require!(
+            new_len >= 8 + data.try_to_vec()?.len(),
+            ErrorCode::InvalidAccountState
+        );
+    }
+    Ok(())
+}
+
+// Move lamports out of the vault with the vault seeds as signer
+fn transfer_from_vault<'info>(
+    wallet: &Account<'info, Wallet>,
+    vault: &UncheckedAccount<'info>,
+    destination: &UncheckedAccount<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    let wallet_key = wallet.key();
+    let seeds = &[VAULT_SEED, wallet_key.as_ref(), &[wallet.nonce]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: vault.to_account_info(),
+                to: destination.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        amount,
+    )
+}
+
+// Build the instruction execute_transaction invokes, with the vault marked as signer
+fn to_vault_instruction(instruction: &ProposedInstruction, vault: &Pubkey) -> Result<Instruction> {
+    // Find vault's position in accounts list
+    let vault_index = instruction
+        .accounts
+        .iter()
+        .position(|acc| acc.pubkey == *vault)
+        .ok_or(ErrorCode::AccountNotFound)?;
+
+    // Prepare account metas with vault as signer
+    let accounts_metas: Vec<AccountMeta> = instruction
+        .accounts
+        .iter()
+        .enumerate()
+        .map(|(idx, acc)| {
+            if idx == vault_index {
+                AccountMeta::new(acc.pubkey, true)
+            } else {
+                acc.to_account_meta()
+            }
+        })
+        .collect();
+
+    Ok(Instruction {
+        program_id: instruction.program_id,
+        accounts: accounts_metas,
+        data: instruction.data.clone(),
+    })
+}
+
+fn find_remaining_account<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|acc| acc.key == key)
+        .ok_or_else(|| error!(ErrorCode::AccountNotFound))
+}
+
+// Deserialize a dependency transaction and check it belongs to the wallet
+fn load_dependency(info: &AccountInfo, wallet: &Pubkey) -> Result<Transaction> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidDependency);
+    let dependency = Transaction::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require_keys_eq!(dependency.wallet, *wallet, ErrorCode::InvalidWallet);
+    Ok(dependency)
+}
+
+fn dependency_met(
+    dependency: &Pubkey,
+    wallet: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<bool> {
+    let dependency_info = find_remaining_account(remaining_accounts, dependency)?;
+    // A dependency can only be closed after it was executed
+    if dependency_info.owner != &crate::ID {
+        return Ok(true);
+    }
+    Ok(load_dependency(dependency_info, wallet)?.executed)
+}
+
+// Sum of all owner weights
+fn sum_owner_weights(owners: &[OwnerConfig]) -> Result<u64> {
+    owners.iter().try_fold(0u64, |total, owner| {
+        total
+            .checked_add(owner.weight)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+    })
+}
+
 // Helper validation functions
-fn validate_owners(owners: &[OwnerConfig], threshold_weight: u64) -> Result<()> {
+fn validate_owners(
+    owners: &[OwnerConfig],
+    threshold_weight: u64,
+    allow_zero_threshold: bool,
+) -> Result<()> {
     require!(!owners.is_empty(), ErrorCode::NoOwners);
+    // Transactions only reserve room for MAX_SIGNERS approvals
+    require!(owners.len() <= MAX_SIGNERS, ErrorCode::TooManyOwners);
     assert_unique_owners(owners)?;
-    require!(threshold_weight > 0, ErrorCode::InvalidThreshold);
+    // A zero threshold makes every transaction executable, so it must be opted into
+    require!(
+        threshold_weight > 0 || allow_zero_threshold,
+        ErrorCode::InvalidThreshold
+    );
 
-    let total_weight: u64 = owners.iter().map(|owner| owner.weight).sum();
+    let total_weight = active_owner_weight(owners)?;
     require!(
         threshold_weight <= total_weight,
         ErrorCode::ThresholdTooHigh
@@ -246,11 +1778,46 @@ fn validate_owners(owners: &[OwnerConfig], threshold_weight: u64) -> Result<()>
     Ok(())
 }
 
+// Expired owners stay listed, but their weight can no longer help reach the threshold
+fn active_owner_weight(owners: &[OwnerConfig]) -> Result<u64> {
+    // The full sum is checked so adding weights can never overflow elsewhere
+    sum_owner_weights(owners)?;
+    let now = Clock::get()?.unix_timestamp;
+    Ok(owners
+        .iter()
+        .filter(|o| !o.is_expired(now))
+        .map(|o| o.weight)
+        .sum())
+}
+
+// An owner that is already expired could never sign
+fn validate_owner_expiries(owners: &[OwnerConfig]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        owners
+            .iter()
+            .all(|o| o.expires_at.map_or(true, |expires_at| expires_at > now)),
+        ErrorCode::InvalidExpiry
+    );
+    Ok(())
+}
+
+fn validate_owner_labels(owners: &[OwnerConfig]) -> Result<()> {
+    for owner in owners {
+        require!(
+            owner.label.len() <= MAX_LABEL_LEN,
+            ErrorCode::InvalidNameLength
+        );
+    }
+    Ok(())
+}
+
 fn validate_instructions(
     instructions: &[ProposedInstruction],
     max_accounts_per_instruction: u8,
     max_data_size: u16,
 ) -> Result<()> {
+    require!(!instructions.is_empty(), ErrorCode::NoInstructions);
     require!(
         instructions.len() <= MAX_INSTRUCTIONS,
         ErrorCode::TooManyInstructions
@@ -273,18 +1840,28 @@ fn validate_instructions(
 fn validate_approval(
     wallet: &Account<Wallet>,
     transaction: &Account<Transaction>,
-    signer: &Signer,
+    signer: &Pubkey,
 ) -> Result<()> {
-    require!(wallet.is_owner(&signer.key()), ErrorCode::NotOwner);
+    require!(wallet.is_owner(signer), ErrorCode::NotOwner);
+    require!(!wallet.is_quarantined(signer), ErrorCode::OwnerQuarantined);
+    require!(
+        !wallet.is_expired(signer, Clock::get()?.unix_timestamp),
+        ErrorCode::OwnerNotFound
+    );
     require!(!transaction.executed, ErrorCode::AlreadyExecuted);
+    require!(!transaction.cancelled, ErrorCode::TransactionCancelled);
     require!(
         wallet.owner_set_seqno == transaction.owner_set_seqno,
         ErrorCode::OwnerSetChanged
     );
     require!(
-        !transaction.signers.contains(&signer.key()),
+        !transaction.signers.contains(signer),
         ErrorCode::AlreadySigned
     );
+    require!(
+        !transaction.rejections.contains(signer),
+        ErrorCode::AlreadyRejected
+    );
 
     Ok(())
 }
@@ -298,6 +1875,99 @@ fn validate_execution(wallet: &Account<Wallet>, transaction: &Account<Transactio
     Ok(())
 }
 
+fn validate_programs(wallet: &Wallet, instructions: &[ProposedInstruction]) -> Result<()> {
+    require!(
+        instructions
+            .iter()
+            .all(|ix| wallet.is_program_allowed(&ix.program_id)),
+        ErrorCode::ProgramNotAllowed
+    );
+    Ok(())
+}
+
+// Instructions for other programs may only write to the vault and allowed destinations.
+// Calls back into this program are governance and are checked where they pay out.
+fn validate_destinations(
+    wallet: &Account<Wallet>,
+    instructions: &[ProposedInstruction],
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if !wallet.enforce_allowlist {
+        return Ok(());
+    }
+    let vault = Pubkey::create_program_address(
+        &[VAULT_SEED, wallet.key().as_ref(), &[wallet.nonce]],
+        &crate::ID,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidWallet))?;
+    let destinations: Vec<Pubkey> = instructions
+        .iter()
+        .filter(|ix| ix.program_id != crate::ID)
+        .flat_map(|ix| ix.accounts.iter())
+        .filter(|acc| acc.is_writable && acc.pubkey != vault)
+        .map(|acc| acc.pubkey)
+        .collect();
+    check_allowlist(wallet, &destinations, remaining_accounts)
+}
+
+// The allowlist is passed among the remaining accounts while the wallet enforces it
+fn check_allowlist(
+    wallet: &Account<Wallet>,
+    destinations: &[Pubkey],
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if !wallet.enforce_allowlist || destinations.is_empty() {
+        return Ok(());
+    }
+    let (address, _) =
+        Pubkey::find_program_address(&[ALLOWLIST_SEED, wallet.key().as_ref()], &crate::ID);
+    let info = find_remaining_account(remaining_accounts, &address)?;
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountNotFound);
+    let allowlist = DestinationAllowlist::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(
+        destinations
+            .iter()
+            .all(|d| allowlist.destinations.contains(d)),
+        ErrorCode::DestinationNotAllowed
+    );
+    Ok(())
+}
+
+// While paused, only proposals that call back into this program
+// (e.g. to unpause) are accepted
+fn validate_not_paused(wallet: &Wallet, instructions: &[ProposedInstruction]) -> Result<()> {
+    require!(
+        !wallet.proposals_paused || instructions.iter().all(|ix| ix.program_id == crate::ID),
+        ErrorCode::ProposalsPaused
+    );
+    Ok(())
+}
+
+// Validate every change before applying any of them
+fn apply_weight_changes(owners: &mut [OwnerConfig], changes: &[WeightChange]) -> Result<()> {
+    for (i, change) in changes.iter().enumerate() {
+        require!(change.weight > 0, ErrorCode::InvalidOwnerWeight);
+        require!(
+            !changes
+                .iter()
+                .skip(i + 1)
+                .any(|item| item.key == change.key),
+            ErrorCode::DuplicateOwner
+        );
+        require!(
+            owners.iter().any(|o| o.key == change.key),
+            ErrorCode::OwnerNotFound
+        );
+    }
+
+    for change in changes.iter() {
+        if let Some(owner) = owners.iter_mut().find(|o| o.key == change.key) {
+            owner.weight = change.weight;
+        }
+    }
+    Ok(())
+}
+
 fn assert_unique_owners(owners: &[OwnerConfig]) -> Result<()> {
     for (i, owner) in owners.iter().enumerate() {
         // Check for non-zero weight
@@ -310,4 +1980,120 @@ fn assert_unique_owners(owners: &[OwnerConfig]) -> Result<()> {
         );
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    fn wallet(num_owners: usize) -> Wallet {
+        Wallet {
+            owners: (0..num_owners)
+                .map(|_| OwnerConfig {
+                    key: Pubkey::new_unique(),
+                    weight: 10,
+                    label: "x".repeat(MAX_LABEL_LEN),
+                    expires_at: Some(0),
+                })
+                .collect(),
+            threshold_weight: 10,
+            nonce: 255,
+            owner_set_seqno: 0,
+            proposals_paused: false,
+            proposer_must_execute: false,
+            allow_zero_threshold: false,
+            created_at: 0,
+            archive_count: 0,
+            recent_executions: [0; RECENT_EXECUTIONS_LEN],
+            pending_key_rotation: None,
+            min_owners: 0,
+            auto_execute: false,
+            execution_delay: 0,
+            enforce_allowlist: false,
+            allowed_programs: Vec::new(),
+            transaction_count: 0,
+            max_transactions: 0,
+        }
+    }
+
+    #[test]
+    fn check_shrink_rejects_sizes_below_the_data() {
+        let wallet = wallet(3);
+        let used = 8 + wallet.try_to_vec().unwrap().len();
+
+        assert!(check_shrink(&wallet, Wallet::space(4), Wallet::space(3)).is_ok());
+        assert!(check_shrink(&wallet, Wallet::space(4), used).is_ok());
+        assert_eq!(
+            check_shrink(&wallet, Wallet::space(4), used - 1).unwrap_err(),
+            ErrorCode::InvalidAccountState.into()
+        );
+        // Growing never truncates, so it is not checked
+        assert!(check_shrink(&wallet, 0, used - 1).is_ok());
+    }
+
+    #[test]
+    fn record_transaction_respects_the_cap_and_overflow() {
+        let mut wallet = wallet(1);
+        wallet.record_transaction().unwrap();
+        assert_eq!(wallet.transaction_count, 1);
+
+        wallet.max_transactions = 2;
+        wallet.record_transaction().unwrap();
+        assert_eq!(
+            wallet.record_transaction().err(),
+            Some(ErrorCode::TransactionLimitReached.into())
+        );
+        assert_eq!(wallet.transaction_count, 2);
+
+        wallet.max_transactions = 0;
+        wallet.transaction_count = u64::MAX;
+        assert_eq!(
+            wallet.record_transaction().err(),
+            Some(ErrorCode::ArithmeticOverflow.into())
+        );
+        assert_eq!(wallet.transaction_count, u64::MAX);
+    }
+
+    #[test]
+    fn legacy_wallet_upgrades_with_defaults() {
+        let owner = Pubkey::new_unique();
+        let legacy = LegacyWallet {
+            owners: vec![LegacyOwnerConfig {
+                key: owner,
+                weight: 60,
+            }],
+            threshold_weight: 50,
+            nonce: 254,
+            owner_set_seqno: 3,
+        };
+        let mut data = Wallet::DISCRIMINATOR.to_vec();
+        data.extend(legacy.try_to_vec().unwrap());
+
+        let wallet = LegacyWallet::load(&data).unwrap().upgrade();
+        assert_eq!(wallet.owners.len(), 1);
+        assert_eq!(wallet.owners[0].key, owner);
+        assert_eq!(wallet.owners[0].weight, 60);
+        assert!(wallet.owners[0].label.is_empty() && wallet.owners[0].expires_at.is_none());
+        assert_eq!(wallet.threshold_weight, 50);
+        assert_eq!(wallet.nonce, 254);
+        assert_eq!(wallet.owner_set_seqno, 3);
+        assert_eq!(wallet.created_at, 0);
+
+        // Trailing bytes mean the account is not in the legacy layout
+        data.push(0);
+        assert!(LegacyWallet::load(&data).is_err());
+    }
+
+    #[test]
+    fn current_wallet_is_not_legacy() {
+        let mut data = Vec::new();
+        wallet(2).try_serialize(&mut data).unwrap();
+        data.resize(Wallet::space(2), 0);
+
+        assert_eq!(
+            LegacyWallet::load(&data).err(),
+            Some(ErrorCode::NotLegacyWallet.into())
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
 use crate::error::ErrorCode;
-use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+use anchor_lang::solana_program::{hash::hashv, instruction::Instruction};
+use anchor_lang::Discriminator;
 
 #[account]
 pub struct Wallet {
@@ -8,12 +10,163 @@ pub struct Wallet {
     pub threshold_weight: u64,
     pub nonce: u8,
     pub owner_set_seqno: u32,
+    pub proposals_paused: bool,
+    pub proposer_must_execute: bool,
+    pub allow_zero_threshold: bool,
+    pub created_at: i64,
+    pub archive_count: u32,
+    // Timestamps of the latest executions, oldest first; 0 marks an empty slot
+    pub recent_executions: [i64; RECENT_EXECUTIONS_LEN],
+    pub pending_key_rotation: Option<KeyRotation>,
+    // Owner count remove_owner and set_owners may not go below; 0 means no minimum
+    pub min_owners: u8,
+    // Execute a transaction inside the approval that makes it executable
+    pub auto_execute: bool,
+    // Seconds every transaction must wait after creation before it can execute
+    pub execution_delay: i64,
+    // Only let the vault send to accounts on the wallet's DestinationAllowlist
+    pub enforce_allowlist: bool,
+    // Programs proposals may invoke besides this one; empty means no restriction
+    pub allowed_programs: Vec<Pubkey>,
+    // Transactions proposed so far, and how many may ever be proposed; 0 means no cap
+    pub transaction_count: u64,
+    pub max_transactions: u64,
 }
 
 impl Wallet {
+    pub fn space(num_owners: usize) -> usize {
+        8 + // discriminator
+        4 + (OwnerConfig::LEN * num_owners) + // owners vec with length prefix
+        8 + // threshold_weight
+        1 + // nonce
+        4 + // owner_set_seqno
+        1 + // proposals_paused
+        1 + // proposer_must_execute
+        1 + // allow_zero_threshold
+        8 + // created_at
+        4 + // archive_count
+        8 * RECENT_EXECUTIONS_LEN + // recent_executions
+        1 + KeyRotation::LEN + // pending_key_rotation option
+        1 + // min_owners
+        1 + // auto_execute
+        8 + // execution_delay
+        1 + // enforce_allowlist
+        4 + (32 * MAX_ALLOWED_PROGRAMS) + // allowed_programs vec with length prefix
+        8 + // transaction_count
+        8 // max_transactions
+    }
+
     pub fn is_owner(&self, key: &Pubkey) -> bool {
         self.owners.iter().any(|o| o.key == *key)
     }
+
+    // Owners are created with a non-zero weight, so zero only comes from quarantine_owner
+    pub fn is_quarantined(&self, key: &Pubkey) -> bool {
+        self.owners.iter().any(|o| o.key == *key && o.weight == 0)
+    }
+
+    // Expired owners stay listed until removed, but can no longer act or add weight
+    pub fn is_expired(&self, key: &Pubkey, now: i64) -> bool {
+        self.owners.iter().any(|o| o.key == *key && o.is_expired(now))
+    }
+
+    // This program stays callable so the wallet can always govern itself
+    pub fn is_program_allowed(&self, program_id: &Pubkey) -> bool {
+        self.allowed_programs.is_empty()
+            || *program_id == crate::ID
+            || self.allowed_programs.contains(program_id)
+    }
+
+    pub fn record_execution(&mut self, timestamp: i64) {
+        self.recent_executions.rotate_left(1);
+        self.recent_executions[RECENT_EXECUTIONS_LEN - 1] = timestamp;
+    }
+
+    // Count a new proposal, refusing it once the cap is reached
+    pub fn record_transaction(&mut self) -> Result<()> {
+        require!(
+            self.max_transactions == 0 || self.transaction_count < self.max_transactions,
+            ErrorCode::TransactionLimitReached
+        );
+        self.transaction_count = self
+            .transaction_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn increment_owner_set_seqno(&mut self) -> Result<()> {
+        self.owner_set_seqno = self
+            .owner_set_seqno
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// Wallet layout from before owner labels, expiries and the wallet settings were added.
+// Only read by migrate_wallet.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyWallet {
+    pub owners: Vec<LegacyOwnerConfig>,
+    pub threshold_weight: u64,
+    pub nonce: u8,
+    pub owner_set_seqno: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyOwnerConfig {
+    pub key: Pubkey,
+    pub weight: u64,
+}
+
+impl LegacyWallet {
+    // Legacy wallets were allocated to fit their data exactly, which also tells them
+    // apart from wallets in the current layout
+    pub fn load(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() > 8 && data[..8] == Wallet::DISCRIMINATOR,
+            ErrorCode::NotLegacyWallet
+        );
+        let mut rest = &data[8..];
+        let legacy =
+            Self::deserialize(&mut rest).map_err(|_| error!(ErrorCode::NotLegacyWallet))?;
+        require!(rest.is_empty(), ErrorCode::NotLegacyWallet);
+        Ok(legacy)
+    }
+
+    // New fields start from their defaults; the creation time is unknown, so it is 0
+    pub fn upgrade(self) -> Wallet {
+        Wallet {
+            owners: self
+                .owners
+                .into_iter()
+                .map(|o| OwnerConfig {
+                    key: o.key,
+                    weight: o.weight,
+                    label: String::new(),
+                    expires_at: None,
+                })
+                .collect(),
+            threshold_weight: self.threshold_weight,
+            nonce: self.nonce,
+            owner_set_seqno: self.owner_set_seqno,
+            proposals_paused: false,
+            proposer_must_execute: false,
+            allow_zero_threshold: false,
+            created_at: 0,
+            archive_count: 0,
+            recent_executions: [0; RECENT_EXECUTIONS_LEN],
+            pending_key_rotation: None,
+            min_owners: 0,
+            auto_execute: false,
+            execution_delay: 0,
+            enforce_allowlist: false,
+            allowed_programs: Vec::new(),
+            transaction_count: 0,
+            max_transactions: 0,
+        }
+    }
 }
 
 #[account]
@@ -24,15 +177,48 @@ pub struct Transaction {
     pub executed: bool,
     pub signers: Vec<Pubkey>,
     pub owner_set_seqno: u32,
+    pub title: String,
+    pub depends_on: Option<Pubkey>,
+    pub final_signer: Option<Pubkey>,
+    // Earliest time the transaction may execute; signing stays open before it
+    pub eta: Option<i64>,
+    pub created_at: i64,
+    // Owners voting against the transaction and their combined weight
+    pub rejections: Vec<Pubkey>,
+    pub rejection_weight: u64,
+    pub cancelled: bool,
+    // Free-form description for approvers, longer than the title
+    pub memo: String,
 }
 
 impl Transaction {
+    pub fn space(max_accounts_per_instruction: usize, max_data_size: usize) -> usize {
+        8 + // discriminator
+        32 + // wallet pubkey
+        32 + // creator
+        1 + // executed
+        4 + (32 * MAX_SIGNERS) + // signers vec with length prefix
+        4 + // owner_set_seqno
+        4 + (ProposedInstruction::size(max_accounts_per_instruction, max_data_size) * MAX_INSTRUCTIONS) + // instructions vec with length prefix
+        4 + MAX_TITLE_LEN + // title string with length prefix
+        1 + 32 + // depends_on option
+        1 + 32 + // final_signer option
+        1 + 8 + // eta option
+        8 + // created_at
+        4 + (32 * MAX_SIGNERS) + // rejections vec with length prefix
+        8 + // rejection_weight
+        1 + // cancelled
+        4 + MAX_MEMO_LEN // memo string with length prefix
+    }
+
     pub fn initialize(
         &mut self,
         instructions: Vec<ProposedInstruction>,
         wallet: Pubkey,
         creator: Pubkey,
         owner_set_seqno: u32,
+        title: String,
+        depends_on: Option<Pubkey>,
     ) {
         self.instructions = instructions;
         self.wallet = wallet;
@@ -40,18 +226,286 @@ impl Transaction {
         self.signers = vec![creator];
         self.owner_set_seqno = owner_set_seqno;
         self.creator = creator;
+        self.title = title;
+        self.depends_on = depends_on;
+        self.final_signer = None;
+        self.eta = None;
+        self.rejections = Vec::new();
+        self.rejection_weight = 0;
+        self.cancelled = false;
+        self.memo = String::new();
+    }
+
+    // Both the transaction's own eta and the wallet's execution delay must have passed
+    pub fn is_unlocked(&self, now: i64, execution_delay: i64) -> bool {
+        now >= self.earliest_execution_at(execution_delay)
+    }
+
+    pub fn earliest_execution_at(&self, execution_delay: i64) -> i64 {
+        let delayed = self.created_at.saturating_add(execution_delay);
+        self.eta.map_or(delayed, |eta| eta.max(delayed))
+    }
+
+    // Binds an approval to this account, its governance epoch and its exact instructions
+    pub fn approval_digest(&self, key: &Pubkey) -> Result<[u8; 32]> {
+        let instructions = self.instructions.try_to_vec()?;
+        Ok(hashv(&[
+            b"power-multisig:approve",
+            key.as_ref(),
+            &self.owner_set_seqno.to_le_bytes(),
+            &instructions,
+        ])
+        .to_bytes())
+    }
+}
+
+// Write-once snapshot of a wallet's governance state
+#[account]
+pub struct WalletArchive {
+    pub wallet: Pubkey,
+    pub index: u32,
+    pub owners: Vec<OwnerConfig>,
+    pub threshold_weight: u64,
+    pub owner_set_seqno: u32,
+    pub archived_at: i64,
+}
+
+impl WalletArchive {
+    pub fn space(num_owners: usize) -> usize {
+        8 + // discriminator
+        32 + // wallet
+        4 + // index
+        4 + (OwnerConfig::LEN * num_owners) + // owners vec with length prefix
+        8 + // threshold_weight
+        4 + // owner_set_seqno
+        8 // archived_at
+    }
+}
+
+// Accounts the vault may send to while the wallet enforces its allowlist
+#[account]
+pub struct DestinationAllowlist {
+    pub wallet: Pubkey,
+    pub destinations: Vec<Pubkey>,
+}
+
+impl DestinationAllowlist {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        4 + (32 * MAX_ALLOWED_DESTINATIONS); // destinations vec with length prefix
+}
+
+// Lamport payment from the vault, approved once and executable by anyone on schedule
+#[account]
+pub struct RecurringPayment {
+    pub wallet: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub interval: i64,
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    pub max_executions: Option<u32>,
+    pub executions_count: u32,
+    pub last_executed: i64,
+    pub cancelled: bool,
+}
+
+impl RecurringPayment {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        32 + // destination
+        8 + // amount
+        8 + // interval
+        8 + // start_time
+        1 + 8 + // end_time option
+        1 + 4 + // max_executions option
+        4 + // executions_count
+        8 + // last_executed
+        1; // cancelled
+
+    // No payment can ever be made again
+    pub fn is_finished(&self, now: i64) -> bool {
+        self.cancelled
+            || self.end_time.is_some_and(|end_time| now > end_time)
+            || self
+                .max_executions
+                .is_some_and(|max| self.executions_count >= max)
     }
+
+    pub fn next_due(&self) -> Result<i64> {
+        if self.executions_count == 0 {
+            return Ok(self.start_time);
+        }
+        self.last_executed
+            .checked_add(self.interval)
+            .ok_or(error!(ErrorCode::ArithmeticOverflow))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RecurringPaymentConfig {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub interval: i64,
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    pub max_executions: Option<u32>,
+}
+
+// Lamports an owner may move from the vault per period without a proposal
+#[account]
+pub struct SpendingLimit {
+    pub wallet: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub period: i64,
+    pub remaining: u64,
+    pub last_reset: i64,
+}
+
+impl SpendingLimit {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        32 + // owner
+        8 + // amount
+        8 + // period
+        8 + // remaining
+        8; // last_reset
+
+    // Start a new period with the full amount once the current one has elapsed
+    pub fn refresh(&mut self, now: i64) {
+        if now >= self.last_reset.saturating_add(self.period) {
+            self.remaining = self.amount;
+            self.last_reset = now;
+        }
+    }
+}
+
+// Marks an off-chain approval as used
+#[account]
+pub struct OffchainApproval {
+    pub transaction: Pubkey,
+    pub owner: Pubkey,
+}
+
+impl OffchainApproval {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // transaction
+        32; // owner
+}
+
+// Left behind by close_wallet so the wallet address cannot be set up again
+#[account]
+pub struct ClosedWallet {
+    pub wallet: Pubkey,
+    pub closed_at: i64,
+}
+
+impl ClosedWallet {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        8; // closed_at
+}
+
+// Proof that an owner approved a transaction, readable by other programs
+#[account]
+pub struct ApprovalReceipt {
+    pub transaction: Pubkey,
+    pub owner: Pubkey,
+    pub weight: u64,
+    pub approved_at: i64,
+    pub bump: u8,
+}
+
+impl ApprovalReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // transaction
+        32 + // owner
+        8 + // weight
+        8 + // approved_at
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransactionInfo {
+    pub wallet: Pubkey,
+    pub creator: Pubkey,
+    pub executed: bool,
+    pub signers: Vec<Pubkey>,
+    pub owner_set_seqno: u32,
+    pub wallet_owner_set_seqno: u32,
+    pub title: String,
+    pub depends_on: Option<Pubkey>,
+    pub final_signer: Option<Pubkey>,
+    pub eta: Option<i64>,
+    pub created_at: i64,
+    pub rejections: Vec<Pubkey>,
+    pub rejection_weight: u64,
+    pub cancelled: bool,
+    pub memo: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateTransactionResult {
+    pub transaction: Pubkey,
+    pub current_weight: u64,
+    pub remaining: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TimelockedTransaction {
+    pub transaction: Pubkey,
+    pub earliest_execution_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WeightGap {
+    pub completes: bool,
+    pub remaining: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProgramInfo {
+    pub version: String,
+    pub features: u64,
+}
+
+// Key swap approved by the wallet, waiting for the new key to accept it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct KeyRotation {
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+impl KeyRotation {
+    pub const LEN: usize = 32 + // old_owner
+        32; // new_owner
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct OwnerConfig {
     pub key: Pubkey,
     pub weight: u64,
+    pub label: String,
+    pub expires_at: Option<i64>,
 }
 
 impl OwnerConfig {
     pub const LEN: usize = 32 + // key
-        8;  // weight
+        8 + // weight
+        4 + MAX_LABEL_LEN + // label string with length prefix
+        1 + 8;  // expires_at option
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+// New weight for an existing owner; its label and expiry are left as they are
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WeightChange {
+    pub key: Pubkey,
+    pub weight: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -0,0 +1,142 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: add-owner", () => {
+  let ctx: TestContext;
+
+  const addOwnerIx = (key: PublicKey, weight: number) =>
+    ctx.program.methods
+      .addOwner({ key, weight: new BN(weight), label: "", expiresAt: null })
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("adds an owner through an approved proposal", async () => {
+    const newOwner = anchor.web3.Keypair.generate();
+    const sizeBefore = (await ctx.provider.connection.getAccountInfo(ctx.wallet.publicKey)).data.length;
+
+    await createAndExecuteProposal(ctx, await addOwnerIx(newOwner.publicKey, 25));
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners).to.have.length(4);
+    expect(wallet.owners[3].key.equals(newOwner.publicKey)).to.be.true;
+    expect(wallet.owners[3].weight.toNumber()).to.equal(25);
+    expect(wallet.ownerSetSeqno).to.equal(1);
+
+    // 钱包账户按新增所有者扩容: key 32 + weight 8 + label 4+32 + expires_at 1+8
+    const sizeAfter = (await ctx.provider.connection.getAccountInfo(ctx.wallet.publicKey)).data.length;
+    expect(sizeAfter).to.equal(sizeBefore + 85);
+  });
+
+  it("rejects an existing owner", async () => {
+    try {
+      await createAndExecuteProposal(ctx, await addOwnerIx(ctx.owners.owner2.publicKey, 5));
+      expect.fail("should have failed with existing owner");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("OwnerAlreadyExists");
+    }
+  });
+
+  it("rejects growing past MAX_SIGNERS", async () => {
+    // 补足到10个所有者
+    for (let i = 0; i < 7; i++) {
+      await createAndExecuteProposal(
+        ctx,
+        await addOwnerIx(anchor.web3.Keypair.generate().publicKey, 1)
+      );
+    }
+
+    try {
+      await createAndExecuteProposal(
+        ctx,
+        await addOwnerIx(anchor.web3.Keypair.generate().publicKey, 1)
+      );
+      expect.fail("should have failed with too many owners");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("TooManyOwners");
+    }
+  });
+
+  it("counts a new owner's weight on pending transactions only once they sign", async () => {
+    const newOwner = anchor.web3.Keypair.generate();
+    const transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    const approve = (transaction: PublicKey, owner: anchor.web3.Keypair) =>
+      ctx.program.methods
+        .approve()
+        .accounts({ wallet: ctx.wallet.publicKey, transaction, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+    const hasExecutable = (transaction: PublicKey) =>
+      ctx.program.methods
+        .hasExecutableTransaction()
+        .accounts({ wallet: ctx.wallet.publicKey })
+        .remainingAccounts([{ pubkey: transaction, isWritable: false, isSigner: false }])
+        .view();
+
+    // owner1(60) + owner2(30) 已达到阈值的待执行交易
+    const before = await proposeTransaction(ctx, [transferIx], { approvers: [ctx.owners.owner2] });
+    expect(await hasExecutable(before)).to.be.true;
+
+    // add_owner 递增 owner_set_seqno, 之前的交易既不能再签名也不能执行
+    await createAndExecuteProposal(ctx, await addOwnerIx(newOwner.publicKey, 25));
+    expect(await hasExecutable(before)).to.be.false;
+    try {
+      await approve(before, newOwner);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("OwnerSetChanged");
+    }
+    try {
+      await executeProposal(ctx, before, [transferIx]);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("OwnerSetChanged");
+    }
+
+    // 在新所有者集下重新提案: owner1(60) 不足阈值70, 新所有者签名后才计入其权重
+    const after = await proposeTransaction(ctx, [transferIx]);
+    expect(await hasExecutable(after)).to.be.false;
+    await approve(after, newOwner);
+    expect(await hasExecutable(after)).to.be.true;
+    await executeProposal(ctx, after, [transferIx]);
+  });
+
+  it("cannot be called directly by an owner", async () => {
+    try {
+      await ctx.program.methods
+        .addOwner({ key: anchor.web3.Keypair.generate().publicKey, weight: new BN(10), label: "", expiresAt: null })
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed without the vault signature");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
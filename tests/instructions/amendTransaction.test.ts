@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+} from "./helper";
+
+describe("power-multisig: amend-transaction", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+
+  const toProposed = (ix: TransactionInstruction) => ({
+    programId: ix.programId,
+    accounts: ix.keys.map(key => ({
+      pubkey: key.pubkey,
+      isSigner: key.isSigner,
+      isWritable: key.isWritable,
+    })),
+    data: Buffer.from(ix.data),
+  });
+
+  const transfer = (to: PublicKey, lamports: number) =>
+    SystemProgram.transfer({ fromPubkey: ctx.vault, toPubkey: to, lamports });
+
+  const amend = (ix: TransactionInstruction) =>
+    ctx.program.methods
+      .amendTransaction([toProposed(ix)], 3, 100)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        creator: ctx.owners.owner1.publicKey,
+      })
+      .signers([ctx.owners.owner1])
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    const proposal = anchor.web3.Keypair.generate();
+    proposalKey = proposal.publicKey;
+    await ctx.program.methods
+      .createTransaction(
+        [toProposed(transfer(ctx.owners.owner3.publicKey, 1_000_000))],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([proposal, ctx.owners.owner1])
+      .rpc();
+  });
+
+  it("amends the instructions before other owners sign", async () => {
+    const corrected = transfer(ctx.owners.owner2.publicKey, 2_000_000);
+    await amend(corrected);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.instructions).to.have.length(1);
+    expect(transaction.instructions[0].accounts[1].pubkey.equals(ctx.owners.owner2.publicKey)).to.be.true;
+    expect(Buffer.from(transaction.instructions[0].data).equals(corrected.data)).to.be.true;
+    expect(transaction.signers).to.have.length(1);
+  });
+
+  it("rejects amending after another owner signed", async () => {
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner3.publicKey,
+      })
+      .signers([ctx.owners.owner3])
+      .rpc();
+
+    try {
+      await amend(transfer(ctx.owners.owner2.publicKey, 2_000_000));
+      expect.fail("should have failed after approval");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AlreadyApproved");
+    }
+  });
+
+  it("clears rejections cast against the old instructions", async () => {
+    await ctx.program.methods
+      .rejectTransaction()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner3.publicKey,
+      })
+      .signers([ctx.owners.owner3])
+      .rpc();
+
+    await amend(transfer(ctx.owners.owner2.publicKey, 2_000_000));
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.rejections).to.be.empty;
+    expect(transaction.rejectionWeight.toNumber()).to.equal(0);
+  });
+});
@@ -0,0 +1,94 @@
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  proposeTransaction,
+} from "./helper";
+
+describe("power-multisig: approval-receipt", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+
+  const receiptAddress = (owner: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt"), proposalKey.toBuffer(), owner.toBuffer()],
+      ctx.program.programId
+    )[0];
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    proposalKey = await proposeTransaction(ctx, [ix]);
+
+    await ctx.program.methods
+      .approveWithReceipt()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        receipt: receiptAddress(ctx.owners.owner2.publicKey),
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+  });
+
+  it("creates a receipt when approving", async () => {
+    const receipt = await ctx.program.account.approvalReceipt.fetch(
+      receiptAddress(ctx.owners.owner2.publicKey)
+    );
+    expect(receipt.transaction.equals(proposalKey)).to.be.true;
+    expect(receipt.owner.equals(ctx.owners.owner2.publicKey)).to.be.true;
+    expect(receipt.weight.toNumber()).to.equal(30);
+    expect(receipt.approvedAt.toNumber()).to.be.greaterThan(0);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.signers.some(s => s.equals(ctx.owners.owner2.publicKey))).to.be.true;
+  });
+
+  it("closes the receipt when the signature is revoked", async () => {
+    const receipt = receiptAddress(ctx.owners.owner2.publicKey);
+
+    await ctx.program.methods
+      .revokeSignature()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        receipt,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+
+    expect(await ctx.provider.connection.getAccountInfo(receipt)).to.be.null;
+
+    // 撤销后权重回落到阈值以下
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.signers.some(s => s.equals(ctx.owners.owner2.publicKey))).to.be.false;
+    expect(transaction.finalSigner).to.be.null;
+  });
+
+  it("rejects revoking without a prior signature", async () => {
+    try {
+      await ctx.program.methods
+        .revokeSignature()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposalKey,
+          receipt: receiptAddress(ctx.owners.owner3.publicKey),
+          owner: ctx.owners.owner3.publicKey,
+        })
+        .signers([ctx.owners.owner3])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("NotSigned");
+    }
+  });
+});
@@ -42,7 +42,7 @@ describe("power-multisig: approve", () => {
 
     // 使用 owner1 创建提案
     await ctx.program.methods
-      .createTransaction([proposedIx])
+      .createTransaction([proposedIx], 3, 100, "", null, null, "")
       .accounts({
         wallet: ctx.wallet.publicKey,
         transaction: proposalKey,
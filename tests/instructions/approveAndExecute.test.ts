@@ -0,0 +1,75 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  proposeTransaction,
+} from "./helper";
+
+describe("power-multisig: approve-and-execute", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  let receiver: anchor.web3.Keypair;
+
+  const approveAndExecute = (owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .approveAndExecute()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: owner.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: ctx.vault, isWritable: true, isSigner: false },
+        { pubkey: receiver.publicKey, isWritable: true, isSigner: false },
+        { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+      ])
+      .signers([owner])
+      .rpc();
+
+  const propose = async (creator: anchor.web3.Keypair) => {
+    const transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: receiver.publicKey,
+      lamports: 0.1 * LAMPORTS_PER_SOL,
+    });
+    proposalKey = await proposeTransaction(ctx, [transferIx], { creator });
+  };
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    receiver = anchor.web3.Keypair.generate();
+  });
+
+  it("approves and executes with the last needed signature", async () => {
+    // owner1(60) + owner2(30) 达到阈值70
+    await propose(ctx.owners.owner1);
+    await approveAndExecute(ctx.owners.owner2);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.true;
+    expect(transaction.signers.some(s => s.equals(ctx.owners.owner2.publicKey))).to.be.true;
+    expect(await ctx.provider.connection.getBalance(receiver.publicKey)).to.equal(
+      0.1 * LAMPORTS_PER_SOL
+    );
+  });
+
+  it("fails while the threshold is still not met", async () => {
+    // owner3(10) + owner2(30) 不足70
+    await propose(ctx.owners.owner3);
+    try {
+      await approveAndExecute(ctx.owners.owner2);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InsufficientSigners");
+    }
+
+    // 指令整体回滚, 签名也不会保留
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.false;
+    expect(transaction.signers.some(s => s.equals(ctx.owners.owner2.publicKey))).to.be.false;
+  });
+});
@@ -0,0 +1,34 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+// SPL Memo 程序, 本地验证器默认加载
+const MEMO_PROGRAM_ID = new PublicKey("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+describe("power-multisig: arbitrary-instruction", () => {
+  let ctx: TestContext;
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("executes a memo signed by the vault", async () => {
+    // Memo 程序会校验列出的账户确实签名, vault 通过 invoke_signed 签名
+    const memoIx = new TransactionInstruction({
+      programId: MEMO_PROGRAM_ID,
+      keys: [{ pubkey: ctx.vault, isSigner: true, isWritable: false }],
+      data: Buffer.from("payroll 2024-06", "utf8"),
+    });
+
+    const proposal = await createAndExecuteProposal(ctx, memoIx);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposal.publicKey);
+    expect(transaction.executed).to.be.true;
+  });
+});
@@ -0,0 +1,75 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: archive-state", () => {
+  let ctx: TestContext;
+
+  const archiveAddress = (index: number) => {
+    const seed = Buffer.alloc(4);
+    seed.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("archive"), ctx.wallet.publicKey.toBuffer(), seed],
+      ctx.program.programId
+    )[0];
+  };
+
+  const archive = async (index: number) => {
+    const ix = await ctx.program.methods
+      .archiveState()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        archive: archiveAddress(index),
+        vault: ctx.vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    await createAndExecuteProposal(ctx, ix);
+  };
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("captures the wallet state at the time of each archive", async () => {
+    await archive(0);
+
+    // 增加所有者后再归档一次
+    const newOwner = anchor.web3.Keypair.generate();
+    const addOwnerIx = await ctx.program.methods
+      .addOwner({ key: newOwner.publicKey, weight: new BN(25), label: "", expiresAt: null })
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    await createAndExecuteProposal(ctx, addOwnerIx);
+
+    await archive(1);
+
+    const first = await ctx.program.account.walletArchive.fetch(archiveAddress(0));
+    expect(first.index).to.equal(0);
+    expect(first.owners).to.have.length(3);
+    expect(first.ownerSetSeqno).to.equal(0);
+    expect(first.thresholdWeight.toNumber()).to.equal(70);
+
+    const second = await ctx.program.account.walletArchive.fetch(archiveAddress(1));
+    expect(second.index).to.equal(1);
+    expect(second.owners).to.have.length(4);
+    expect(second.owners[3].key.equals(newOwner.publicKey)).to.be.true;
+    expect(second.ownerSetSeqno).to.equal(1);
+    expect(second.archivedAt.toNumber()).to.be.at.least(first.archivedAt.toNumber());
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.archiveCount).to.equal(2);
+  });
+});
@@ -0,0 +1,108 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+} from "./helper";
+
+describe("power-multisig: auto-execute", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  let receiver: anchor.web3.Keypair;
+
+  const executeAccounts = () => [
+    { pubkey: ctx.vault, isWritable: true, isSigner: false },
+    { pubkey: receiver.publicKey, isWritable: true, isSigner: false },
+    { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+  ];
+
+  const approve = (withAccounts: boolean) =>
+    ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .remainingAccounts(withAccounts ? executeAccounts() : [])
+      .signers([ctx.owners.owner2])
+      .rpc();
+
+  const setAutoExecute = async (enabled: boolean) =>
+    createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .setAutoExecute(enabled)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    // owner1 提案, 等待 owner2 的批准达到阈值
+    receiver = anchor.web3.Keypair.generate();
+    const transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: receiver.publicKey,
+      lamports: 0.1 * LAMPORTS_PER_SOL,
+    });
+    await setAutoExecute(true);
+    proposalKey = await proposeTransaction(ctx, [transferIx]);
+  });
+
+  it("executes with the approval that crosses the threshold", async () => {
+    await approve(true);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.true;
+    expect(await ctx.provider.connection.getBalance(receiver.publicKey)).to.equal(
+      0.1 * LAMPORTS_PER_SOL
+    );
+  });
+
+  it("only signs when the execute accounts are missing", async () => {
+    await approve(false);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.false;
+    expect(transaction.signers.some(s => s.equals(ctx.owners.owner2.publicKey))).to.be.true;
+
+    // 之后仍可单独执行
+    await ctx.program.methods
+      .executeTransaction()
+      .accounts({
+        transaction: proposalKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .remainingAccounts(executeAccounts())
+      .signers([ctx.owners.owner1])
+      .rpc();
+    expect((await ctx.program.account.transaction.fetch(proposalKey)).executed).to.be.true;
+  });
+
+  it("only signs when auto execution is disabled", async () => {
+    await setAutoExecute(false);
+    // 修改配置不递增序号, 待处理交易仍然有效
+    await approve(true);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.false;
+  });
+
+  it("can be enabled when the wallet is created", async () => {
+    const fresh = await initializeContext();
+    await createMultisigWallet(fresh, undefined, 70, false, 0, true);
+
+    const wallet = await fresh.program.account.wallet.fetch(fresh.wallet.publicKey);
+    expect(wallet.autoExecute).to.be.true;
+  });
+});
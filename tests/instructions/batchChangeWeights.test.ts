@@ -0,0 +1,104 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: batch-change-weights", () => {
+  let ctx: TestContext;
+
+  const batchChange = async (changes: { key: PublicKey; weight: number }[]) => {
+    const ix = await ctx.program.methods
+      .batchChangeWeights(changes.map(c => ({ key: c.key, weight: new BN(c.weight) })))
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+    await createAndExecuteProposal(ctx, ix);
+  };
+
+  beforeEach(async () => {
+    // owner1(60) owner2(30) owner3(10), 阈值70
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("applies changes whose intermediate states would be invalid", async () => {
+    // 单独先把 owner1 改为 20 会使总权重降到 60 < 70
+    await batchChange([
+      { key: ctx.owners.owner1.publicKey, weight: 20 },
+      { key: ctx.owners.owner2.publicKey, weight: 60 },
+    ]);
+
+    const walletAccount = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(walletAccount.owners[0].weight.toNumber()).to.equal(20);
+    expect(walletAccount.owners[1].weight.toNumber()).to.equal(60);
+    expect(walletAccount.owners[2].weight.toNumber()).to.equal(10);
+    expect(walletAccount.ownerSetSeqno).to.equal(1);
+  });
+
+  it("fails when the final total weight is below the threshold", async () => {
+    try {
+      await batchChange([
+        { key: ctx.owners.owner1.publicKey, weight: 20 },
+        { key: ctx.owners.owner2.publicKey, weight: 20 },
+      ]);
+      expect.fail("should have failed with threshold too high");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("ThresholdTooHigh");
+    }
+  });
+
+  it("fails with a zero weight", async () => {
+    try {
+      await batchChange([{ key: ctx.owners.owner3.publicKey, weight: 0 }]);
+      expect.fail("should have failed with invalid owner weight");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("InvalidOwnerWeight");
+    }
+  });
+
+  it("fails when an owner is listed twice", async () => {
+    try {
+      await batchChange([
+        { key: ctx.owners.owner2.publicKey, weight: 40 },
+        { key: ctx.owners.owner2.publicKey, weight: 50 },
+      ]);
+      expect.fail("should have failed with duplicate owner");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("DuplicateOwner");
+    }
+  });
+
+  it("fails for an unknown owner", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    try {
+      await batchChange([{ key: stranger.publicKey, weight: 10 }]);
+      expect.fail("should have failed with owner not found");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("OwnerNotFound");
+    }
+  });
+
+  it("cannot be called directly by an owner", async () => {
+    try {
+      await ctx.program.methods
+        .batchChangeWeights([{ key: ctx.owners.owner1.publicKey, weight: new BN(100) }])
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed without the vault signature");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -0,0 +1,85 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: batch-execution", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  const receiverA = anchor.web3.Keypair.generate().publicKey;
+  const receiverB = anchor.web3.Keypair.generate().publicKey;
+
+  const transfer = (to: PublicKey, lamports: number) =>
+    SystemProgram.transfer({ fromPubkey: ctx.vault, toPubkey: to, lamports });
+
+  // 创建包含多条指令的提案并让 owner2 批准
+  const propose = async (instructions: TransactionInstruction[]) => {
+    proposalKey = await proposeTransaction(ctx, instructions, { approvers: [ctx.owners.owner2] });
+  };
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("runs every instruction of the batch in order", async () => {
+    await propose([
+      transfer(receiverA, 0.1 * LAMPORTS_PER_SOL),
+      transfer(receiverB, 0.2 * LAMPORTS_PER_SOL),
+    ]);
+    await executeProposal(ctx, proposalKey, [
+      transfer(receiverA, 0.1 * LAMPORTS_PER_SOL),
+      transfer(receiverB, 0.2 * LAMPORTS_PER_SOL),
+    ]);
+
+    const connection = ctx.provider.connection;
+    expect(await connection.getBalance(receiverA)).to.equal(0.1 * LAMPORTS_PER_SOL);
+    expect(await connection.getBalance(receiverB)).to.equal(0.2 * LAMPORTS_PER_SOL);
+  });
+
+  it("reverts the whole batch when a later instruction fails", async () => {
+    // 第二笔转账超出 vault 余额
+    await propose([
+      transfer(receiverA, 0.1 * LAMPORTS_PER_SOL),
+      transfer(receiverB, 100 * LAMPORTS_PER_SOL),
+    ]);
+    try {
+      await executeProposal(ctx, proposalKey, [
+        transfer(receiverA, 0.1 * LAMPORTS_PER_SOL),
+        transfer(receiverB, 100 * LAMPORTS_PER_SOL),
+      ]);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("insufficient lamports");
+    }
+
+    expect(await ctx.provider.connection.getBalance(receiverA)).to.equal(0);
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.false;
+  });
+
+  it("reports an account missing from the batch", async () => {
+    await propose([
+      transfer(receiverA, 0.1 * LAMPORTS_PER_SOL),
+      transfer(receiverB, 0.2 * LAMPORTS_PER_SOL),
+    ]);
+    try {
+      // 只传第一笔转账的账户, 缺少 receiverB
+      await executeProposal(ctx, proposalKey, [transfer(receiverA, 0.1 * LAMPORTS_PER_SOL)]);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AccountNotFound");
+    }
+  });
+});
@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: change-owner-weight", () => {
+  let ctx: TestContext;
+
+  const changeWeightIx = (owner: PublicKey, weight: number) =>
+    ctx.program.methods
+      .changeOwnerWeight(owner, new BN(weight))
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("changes a weight through an approved proposal", async () => {
+    await createAndExecuteProposal(ctx, await changeWeightIx(ctx.owners.owner3.publicKey, 25));
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    const owner3 = wallet.owners.find(o => o.key.equals(ctx.owners.owner3.publicKey));
+    expect(owner3.weight.toNumber()).to.equal(25);
+    expect(wallet.ownerSetSeqno).to.equal(1);
+  });
+
+  it("invalidates transactions pending under the old weights", async () => {
+    const pending = anchor.web3.Keypair.generate();
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    await ctx.program.methods
+      .createTransaction(
+        [{
+          programId: ix.programId,
+          accounts: ix.keys.map(key => ({
+            pubkey: key.pubkey,
+            isSigner: key.isSigner,
+            isWritable: key.isWritable,
+          })),
+          data: Buffer.from(ix.data),
+        }],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: pending.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([pending, ctx.owners.owner1])
+      .rpc();
+
+    await createAndExecuteProposal(ctx, await changeWeightIx(ctx.owners.owner1.publicKey, 70));
+
+    // owner1 现在单独达到阈值, 但旧提案已失效
+    try {
+      await ctx.program.methods
+        .executeTransaction()
+        .accounts({
+          transaction: pending.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .remainingAccounts([
+          ...ix.keys.map(key => ({ ...key, isSigner: false })),
+          { pubkey: ix.programId, isWritable: false, isSigner: false },
+        ])
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed with owner set changed");
+    } catch (error) {
+      expect(error.toString()).to.include("OwnerSetChanged");
+    }
+  });
+
+  it("refuses a weight that leaves the threshold unreachable", async () => {
+    // owner1 降到10后总权重50 < 阈值70
+    try {
+      await createAndExecuteProposal(ctx, await changeWeightIx(ctx.owners.owner1.publicKey, 10));
+      expect.fail("should have failed with threshold too high");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("ThresholdTooHigh");
+    }
+  });
+
+  it("cannot be called directly by an owner", async () => {
+    try {
+      await ctx.program.methods
+        .changeOwnerWeight(ctx.owners.owner1.publicKey, new BN(100))
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed without the vault signature");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -0,0 +1,125 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: change-threshold", () => {
+  let ctx: TestContext;
+
+  const changeThresholdIx = (threshold: number) =>
+    ctx.program.methods
+      .changeThreshold(new BN(threshold))
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("changes the threshold through an approved proposal", async () => {
+    await createAndExecuteProposal(ctx, await changeThresholdIx(90));
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.thresholdWeight.toNumber()).to.equal(90);
+    expect(wallet.ownerSetSeqno).to.equal(1);
+  });
+
+  it("stops a transaction that only met the old threshold", async () => {
+    // owner1(60) + owner3(10) 满足旧阈值70
+    const pending = anchor.web3.Keypair.generate();
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    await ctx.program.methods
+      .createTransaction(
+        [{
+          programId: ix.programId,
+          accounts: ix.keys.map(key => ({
+            pubkey: key.pubkey,
+            isSigner: key.isSigner,
+            isWritable: key.isWritable,
+          })),
+          data: Buffer.from(ix.data),
+        }],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: pending.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([pending, ctx.owners.owner1])
+      .rpc();
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: pending.publicKey,
+        owner: ctx.owners.owner3.publicKey,
+      })
+      .signers([ctx.owners.owner3])
+      .rpc();
+
+    await createAndExecuteProposal(ctx, await changeThresholdIx(90));
+
+    try {
+      await ctx.program.methods
+        .executeTransaction()
+        .accounts({
+          transaction: pending.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .remainingAccounts([
+          ...ix.keys.map(key => ({ ...key, isSigner: false })),
+          { pubkey: ix.programId, isWritable: false, isSigner: false },
+        ])
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed with owner set changed");
+    } catch (error) {
+      expect(error.toString()).to.include("OwnerSetChanged");
+    }
+  });
+
+  it("rejects a threshold above the total weight", async () => {
+    try {
+      await createAndExecuteProposal(ctx, await changeThresholdIx(101));
+      expect.fail("should have failed with threshold too high");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("ThresholdTooHigh");
+    }
+  });
+
+  it("cannot be called directly by an owner", async () => {
+    try {
+      await ctx.program.methods
+        .changeThreshold(new BN(10))
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed without the vault signature");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -0,0 +1,133 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: close-transaction", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  let transferIx: TransactionInstruction;
+
+  const approve = (owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  const reject = (owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .rejectTransaction()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  const execute = () => executeProposal(ctx, proposalKey, [transferIx]);
+
+  const close = (closer: anchor.web3.Keypair, recipient = ctx.owners.owner1.publicKey) =>
+    ctx.program.methods
+      .closeTransaction()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        recipient,
+        owner: closer.publicKey,
+      })
+      .signers([closer])
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    proposalKey = await proposeTransaction(ctx, [transferIx]);
+  });
+
+  it("lets any owner close an executed transaction, refunding the creator", async () => {
+    await approve(ctx.owners.owner2);
+    await execute();
+
+    const rent = await ctx.provider.connection.getBalance(proposalKey);
+    const creatorBefore = await ctx.provider.connection.getBalance(ctx.owners.owner1.publicKey);
+
+    // owner2 关闭, 租金退还给创建者 owner1
+    await close(ctx.owners.owner2);
+
+    expect(await ctx.program.account.transaction.fetchNullable(proposalKey)).to.be.null;
+    const creatorAfter = await ctx.provider.connection.getBalance(ctx.owners.owner1.publicKey);
+    expect(creatorAfter - creatorBefore).to.equal(rent);
+  });
+
+  it("shrinks a cancelled transaction to a tombstone", async () => {
+    await reject(ctx.owners.owner2);
+    await reject(ctx.owners.owner3);
+
+    const connection = ctx.provider.connection;
+    const rent = await connection.getBalance(proposalKey);
+    const creatorBefore = await connection.getBalance(ctx.owners.owner1.publicKey);
+
+    await close(ctx.owners.owner3);
+
+    // 账户保留以便依赖它的交易仍能看到它未执行, 多余的租金退还给创建者
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.cancelled).to.be.true;
+    expect(transaction.executed).to.be.false;
+    expect(transaction.instructions).to.be.empty;
+    const remaining = await connection.getBalance(proposalKey);
+    const creatorAfter = await connection.getBalance(ctx.owners.owner1.publicKey);
+    expect(creatorAfter - creatorBefore).to.equal(rent - remaining);
+    expect(remaining).to.be.lessThan(rent);
+  });
+
+  it("cannot close a pending transaction", async () => {
+    try {
+      await close(ctx.owners.owner1);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("TransactionNotExecuted");
+    }
+  });
+
+  it("only refunds the creator", async () => {
+    await approve(ctx.owners.owner2);
+    await execute();
+
+    try {
+      await close(ctx.owners.owner2, ctx.owners.owner2.publicKey);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidRentRecipient");
+    }
+  });
+
+  it("rejects closing by a non-owner", async () => {
+    await approve(ctx.owners.owner2);
+    await execute();
+
+    const outsider = anchor.web3.Keypair.generate();
+    try {
+      await close(outsider);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnauthorizedClose");
+    }
+  });
+});
@@ -0,0 +1,175 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: close-wallet", () => {
+  let ctx: TestContext;
+  let destination: PublicKey;
+  let rentRecipient: PublicKey;
+
+  const closeWalletIx = () =>
+    ctx.program.methods
+      .closeWallet()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+        destination,
+        rentRecipient,
+      })
+      .instruction();
+
+  const closedWalletAddress = () =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("closed-wallet"), ctx.wallet.publicKey.toBuffer()],
+      ctx.program.programId
+    )[0];
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    destination = anchor.web3.Keypair.generate().publicKey;
+    rentRecipient = anchor.web3.Keypair.generate().publicKey;
+  });
+
+  it("empties the vault and closes the wallet", async () => {
+    const connection = ctx.provider.connection;
+    const vaultBalance = await connection.getBalance(ctx.vault);
+    const walletRent = await connection.getBalance(ctx.wallet.publicKey);
+
+    await createAndExecuteProposal(ctx, await closeWalletIx());
+
+    // vault 支付 ClosedWallet 标记的租金, 其余转给 destination
+    const marker = await connection.getAccountInfo(closedWalletAddress());
+    expect(await connection.getBalance(ctx.vault)).to.equal(0);
+    expect(await connection.getBalance(destination)).to.equal(vaultBalance - marker.lamports);
+    expect(await connection.getBalance(rentRecipient)).to.equal(walletRent);
+    expect(await ctx.program.account.wallet.fetchNullable(ctx.wallet.publicKey)).to.be.null;
+
+    const closed = await ctx.program.account.closedWallet.fetch(closedWalletAddress());
+    expect(closed.wallet.equals(ctx.wallet.publicKey)).to.be.true;
+  });
+
+  it("cannot be created again at the same address", async () => {
+    await createAndExecuteProposal(ctx, await closeWalletIx());
+
+    try {
+      await createMultisigWallet(ctx);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("WalletClosed");
+    }
+    expect(await ctx.program.account.wallet.fetchNullable(ctx.wallet.publicKey)).to.be.null;
+  });
+
+  it("leaves pending transactions unusable", async () => {
+    // 关闭前创建一个待审批的提案
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    const orphan = await proposeTransaction(ctx, [ix]);
+
+    await createAndExecuteProposal(ctx, await closeWalletIx());
+
+    try {
+      await ctx.program.methods
+        .approve()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: orphan,
+          owner: ctx.owners.owner2.publicKey,
+        })
+        .signers([ctx.owners.owner2])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+
+    try {
+      await executeProposal(ctx, orphan, [ix]);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("lets anyone return an orphaned transaction's rent to its creator", async () => {
+    const connection = ctx.provider.connection;
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    // owner2 创建的待审批提案, 钱包关闭后无法再执行
+    const orphan = await proposeTransaction(ctx, [ix], { creator: ctx.owners.owner2 });
+    const rent = await connection.getBalance(orphan);
+
+    await createAndExecuteProposal(ctx, await closeWalletIx());
+
+    // 由测试的 provider 钱包发起, 不需要所有者签名
+    const creatorBalance = await connection.getBalance(ctx.owners.owner2.publicKey);
+    await ctx.program.methods
+      .closeOrphanedTransaction()
+      .accountsPartial({
+        transaction: orphan,
+        closedWallet: closedWalletAddress(),
+        recipient: ctx.owners.owner2.publicKey,
+      })
+      .rpc();
+
+    expect(await connection.getAccountInfo(orphan)).to.be.null;
+    expect(await connection.getBalance(ctx.owners.owner2.publicKey)).to.equal(creatorBalance + rent);
+  });
+
+  it("does not close transactions of a wallet that is still open", async () => {
+    const orphan = await proposeTransaction(ctx, [
+      SystemProgram.transfer({
+        fromPubkey: ctx.vault,
+        toPubkey: ctx.owners.owner3.publicKey,
+        lamports: 1_000_000,
+      }),
+    ]);
+
+    try {
+      await ctx.program.methods
+        .closeOrphanedTransaction()
+        .accountsPartial({
+          transaction: orphan,
+          closedWallet: closedWalletAddress(),
+          recipient: ctx.owners.owner1.publicKey,
+        })
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("cannot be closed without a proposal", async () => {
+    try {
+      await ctx.program.methods
+        .closeWallet()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+          destination,
+          rentRecipient,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
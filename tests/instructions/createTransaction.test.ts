@@ -8,6 +8,7 @@ import {
   TestContext,
   initializeContext,
   createMultisigWallet,
+  proposeTransaction,
 } from "../helper";
 
 describe("power-multisig: create-transaction", () => {
@@ -39,7 +40,7 @@ describe("power-multisig: create-transaction", () => {
     };
 
     await ctx.program.methods
-      .createTransaction([proposedIx])
+      .createTransaction([proposedIx], 3, 100, "", null, null, "")
       .accounts({
         wallet: ctx.wallet.publicKey,
         transaction: proposal.publicKey,
@@ -86,7 +87,7 @@ describe("power-multisig: create-transaction", () => {
     }));
 
     await ctx.program.methods
-      .createTransaction(proposedInstructions)
+      .createTransaction(proposedInstructions, 3, 100, "", null, null, "")
       .accounts({
         wallet: ctx.wallet.publicKey,
         transaction: proposal.publicKey,
@@ -128,7 +129,7 @@ describe("power-multisig: create-transaction", () => {
 
     try {
       await ctx.program.methods
-        .createTransaction([proposedIx])
+        .createTransaction([proposedIx], 3, 100, "", null, null, "")
         .accounts({
           wallet: ctx.wallet.publicKey,
           transaction: proposal.publicKey,
@@ -165,7 +166,7 @@ describe("power-multisig: create-transaction", () => {
     };
 
     await ctx.program.methods
-      .createTransaction([proposedIx])
+      .createTransaction([proposedIx], 3, 100, "", null, null, "")
       .accounts({
         wallet: ctx.wallet.publicKey,
         transaction: proposal.publicKey,
@@ -181,4 +182,237 @@ describe("power-multisig: create-transaction", () => {
     expect(txAccount.signers).to.deep.equal([ctx.owners.owner1.publicKey]);
     expect(txAccount.wallet.equals(ctx.wallet.publicKey)).to.be.true;
   });
-});
\ No newline at end of file
+
+  it("estimates the rent charged for the transaction account", async () => {
+    const proposal = anchor.web3.Keypair.generate();
+    const instruction = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner2.publicKey,
+      lamports: 1_000_000,
+    });
+
+    const proposedIx = {
+      programId: instruction.programId,
+      accounts: instruction.keys.map(key => ({
+        pubkey: key.pubkey,
+        isSigner: key.isSigner,
+        isWritable: key.isWritable,
+      })),
+      data: Buffer.from(instruction.data),
+    };
+
+    const estimate = await ctx.program.methods
+      .estimateTransactionRent(3, 100)
+      .view();
+
+    await ctx.program.methods
+      .createTransaction([proposedIx], 3, 100, "", null, null, "")
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposal.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([proposal, ctx.owners.owner1])
+      .rpc();
+
+    const rentCharged = await ctx.provider.connection.getBalance(proposal.publicKey);
+    expect(estimate.toNumber()).to.equal(rentCharged);
+  });
+
+  it("stores the transaction title", async () => {
+    const proposal = anchor.web3.Keypair.generate();
+    const instruction = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner2.publicKey,
+      lamports: 1_000_000,
+    });
+
+    const proposedIx = {
+      programId: instruction.programId,
+      accounts: instruction.keys.map(key => ({
+        pubkey: key.pubkey,
+        isSigner: key.isSigner,
+        isWritable: key.isWritable,
+      })),
+      data: Buffer.from(instruction.data),
+    };
+
+    await ctx.program.methods
+      .createTransaction([proposedIx], 3, 100, "Q3 payroll", null, null, "")
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposal.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([proposal, ctx.owners.owner1])
+      .rpc();
+
+    const txAccount = await ctx.program.account.transaction.fetch(proposal.publicKey);
+    expect(txAccount.title).to.equal("Q3 payroll");
+  });
+
+  it("fails when the title is too long", async () => {
+    const proposal = anchor.web3.Keypair.generate();
+    const instruction = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner2.publicKey,
+      lamports: 1_000_000,
+    });
+
+    const proposedIx = {
+      programId: instruction.programId,
+      accounts: instruction.keys.map(key => ({
+        pubkey: key.pubkey,
+        isSigner: key.isSigner,
+        isWritable: key.isWritable,
+      })),
+      data: Buffer.from(instruction.data),
+    };
+
+    try {
+      await ctx.program.methods
+        .createTransaction([proposedIx], 3, 100, "x".repeat(65), null, null, "")
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposal.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .signers([proposal, ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed with title too long");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: TitleTooLong");
+    }
+  });
+
+  it("stores the transaction memo", async () => {
+    const memo = "按季度支付审计费用, 详见附件发票 #2024-Q3";
+    const proposal = await proposeTransaction(
+      ctx,
+      [
+        SystemProgram.transfer({
+          fromPubkey: ctx.vault,
+          toPubkey: ctx.owners.owner2.publicKey,
+          lamports: 1_000_000,
+        }),
+      ],
+      { memo }
+    );
+
+    const txAccount = await ctx.program.account.transaction.fetch(proposal);
+    expect(txAccount.memo).to.equal(memo);
+    const info = await ctx.program.methods
+      .getTransaction()
+      .accounts({ wallet: ctx.wallet.publicKey, transaction: proposal })
+      .view();
+    expect(info.memo).to.equal(memo);
+  });
+
+  it("fails when the memo is too long", async () => {
+    try {
+      await proposeTransaction(
+        ctx,
+        [
+          SystemProgram.transfer({
+            fromPubkey: ctx.vault,
+            toPubkey: ctx.owners.owner2.publicKey,
+            lamports: 1_000_000,
+          }),
+        ],
+        { memo: "x".repeat(257) }
+      );
+      expect.fail("should have failed with memo too long");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("DataTooLarge");
+    }
+  });
+
+  it("returns the weight still needed after proposing", async () => {
+    const proposal = anchor.web3.Keypair.generate();
+    const instruction = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner2.publicKey,
+      lamports: 1_000_000,
+    });
+
+    const signature = await ctx.program.methods
+      .createTransaction(
+        [{
+          programId: instruction.programId,
+          accounts: instruction.keys.map(key => ({
+            pubkey: key.pubkey,
+            isSigner: key.isSigner,
+            isWritable: key.isWritable,
+          })),
+          data: Buffer.from(instruction.data),
+        }],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposal.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([proposal, ctx.owners.owner1])
+      .rpc({ commitment: "confirmed" });
+
+    const tx = await ctx.provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    // 返回数据: transaction(32) + current_weight(u64) + remaining(u64)
+    const data = Buffer.from(tx.meta.returnData.data[0], "base64");
+    expect(new PublicKey(data.subarray(0, 32)).equals(proposal.publicKey)).to.be.true;
+    expect(new BN(data.subarray(32, 40), "le").toNumber()).to.equal(60);
+    expect(new BN(data.subarray(40, 48), "le").toNumber()).to.equal(70 - 60);
+  });
+
+  describe("instruction count limits", () => {
+    // 最小的空指令, 避免超出交易大小限制
+    const emptyIx = {
+      programId: SystemProgram.programId,
+      accounts: [],
+      data: Buffer.alloc(0),
+    };
+
+    const create = (count: number) => {
+      const proposal = anchor.web3.Keypair.generate();
+      return ctx.program.methods
+        .createTransaction(Array(count).fill(emptyIx), 0, 0, "", null, null, "")
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposal.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .signers([proposal, ctx.owners.owner1])
+        .rpc();
+    };
+
+    it("fails with no instructions", async () => {
+      try {
+        await create(0);
+        expect.fail("should have failed with no instructions");
+      } catch (error) {
+        expect(error.toString()).to.include("Error Code: NoInstructions");
+      }
+    });
+
+    it("accepts exactly the maximum number of instructions", async () => {
+      await create(5);
+    });
+
+    it("fails with more than the maximum number of instructions", async () => {
+      try {
+        await create(6);
+        expect.fail("should have failed with too many instructions");
+      } catch (error) {
+        expect(error.toString()).to.include("Error Code: TooManyInstructions");
+      }
+    });
+  });
+});
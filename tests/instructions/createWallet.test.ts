@@ -1,10 +1,15 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { MultisigWallet } from "../../target/types/multisig_wallet";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { BN } from "bn.js";
 import { expect } from "chai";
-import { TestContext, initializeContext } from "../helper";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "../helper";
 
 describe("power-multisig: create-wallet", () => {
   let ctx: TestContext;
@@ -15,14 +20,14 @@ describe("power-multisig: create-wallet", () => {
 
   it("successfully creates wallet with valid params", async () => {
     const owners = [
-      { key: ctx.owners.owner1.publicKey, weight: new BN(60) },
-      { key: ctx.owners.owner2.publicKey, weight: new BN(30) },
-      { key: ctx.owners.owner3.publicKey, weight: new BN(10) },
+      { key: ctx.owners.owner1.publicKey, weight: new BN(60), label: "", expiresAt: null },
+      { key: ctx.owners.owner2.publicKey, weight: new BN(30), label: "", expiresAt: null },
+      { key: ctx.owners.owner3.publicKey, weight: new BN(10), label: "", expiresAt: null },
     ];
     const threshold = new BN(70);
 
     await ctx.program.methods
-      .createWallet(owners, threshold)
+      .createWallet(owners, threshold, false, 0, false, new BN(0))
       .accounts({
         wallet: ctx.wallet.publicKey,
 
@@ -45,13 +50,13 @@ describe("power-multisig: create-wallet", () => {
 
   it("fails with duplicate owners", async () => {
     const owners = [
-      { key: ctx.owners.owner1.publicKey, weight: new BN(60) },
-      { key: ctx.owners.owner1.publicKey, weight: new BN(40) },
+      { key: ctx.owners.owner1.publicKey, weight: new BN(60), label: "", expiresAt: null },
+      { key: ctx.owners.owner1.publicKey, weight: new BN(40), label: "", expiresAt: null },
     ];
 
     try {
       await ctx.program.methods
-        .createWallet(owners, new BN(51))
+        .createWallet(owners, new BN(51), false, 0, false, new BN(0))
         .accounts({
           wallet: ctx.wallet.publicKey,
 
@@ -68,7 +73,7 @@ describe("power-multisig: create-wallet", () => {
   it("fails with no owners", async () => {
     try {
       await ctx.program.methods
-        .createWallet([], new BN(1))
+        .createWallet([], new BN(1), false, 0, false, new BN(0))
         .accounts({
           wallet: ctx.wallet.publicKey,
 
@@ -85,13 +90,13 @@ describe("power-multisig: create-wallet", () => {
 
   it("fails with zero weight owner", async () => {
     const owners = [
-      { key: ctx.owners.owner1.publicKey, weight: new BN(0) },
-      { key: ctx.owners.owner2.publicKey, weight: new BN(50) },
+      { key: ctx.owners.owner1.publicKey, weight: new BN(0), label: "", expiresAt: null },
+      { key: ctx.owners.owner2.publicKey, weight: new BN(50), label: "", expiresAt: null },
     ];
 
     try {
       await ctx.program.methods
-        .createWallet(owners, new BN(51))
+        .createWallet(owners, new BN(51), false, 0, false, new BN(0))
         .accounts({
           wallet: ctx.wallet.publicKey,
 
@@ -108,14 +113,14 @@ describe("power-multisig: create-wallet", () => {
 
   it("fails with threshold higher than total weight", async () => {
     const owners = [
-      { key: ctx.owners.owner1.publicKey, weight: new BN(30) },
-      { key: ctx.owners.owner2.publicKey, weight: new BN(20) },
+      { key: ctx.owners.owner1.publicKey, weight: new BN(30), label: "", expiresAt: null },
+      { key: ctx.owners.owner2.publicKey, weight: new BN(20), label: "", expiresAt: null },
     ];
     const threshold = new BN(51);
 
     try {
       await ctx.program.methods
-        .createWallet(owners, threshold)
+        .createWallet(owners, threshold, false, 0, false, new BN(0))
         .accounts({
           wallet: ctx.wallet.publicKey,
 
@@ -133,13 +138,13 @@ describe("power-multisig: create-wallet", () => {
 
   it("fails with zero threshold", async () => {
     const owners = [
-      { key: ctx.owners.owner1.publicKey, weight: new BN(60) },
-      { key: ctx.owners.owner2.publicKey, weight: new BN(40) },
+      { key: ctx.owners.owner1.publicKey, weight: new BN(60), label: "", expiresAt: null },
+      { key: ctx.owners.owner2.publicKey, weight: new BN(40), label: "", expiresAt: null },
     ];
 
     try {
       await ctx.program.methods
-        .createWallet(owners, new BN(0))
+        .createWallet(owners, new BN(0), false, 0, false, new BN(0))
         .accounts({
           wallet: ctx.wallet.publicKey,
 
@@ -155,13 +160,13 @@ describe("power-multisig: create-wallet", () => {
 
   it("creates wallet with minimum valid threshold", async () => {
     const owners = [
-      { key: ctx.owners.owner1.publicKey, weight: new BN(60) },
-      { key: ctx.owners.owner2.publicKey, weight: new BN(40) },
+      { key: ctx.owners.owner1.publicKey, weight: new BN(60), label: "", expiresAt: null },
+      { key: ctx.owners.owner2.publicKey, weight: new BN(40), label: "", expiresAt: null },
     ];
     const threshold = new BN(1);
 
     await ctx.program.methods
-      .createWallet(owners, threshold)
+      .createWallet(owners, threshold, false, 0, false, new BN(0))
       .accounts({
         wallet: ctx.wallet.publicKey,
 
@@ -178,13 +183,13 @@ describe("power-multisig: create-wallet", () => {
 
   it("creates wallet with maximum valid threshold", async () => {
     const owners = [
-      { key: ctx.owners.owner1.publicKey, weight: new BN(60) },
-      { key: ctx.owners.owner2.publicKey, weight: new BN(40) },
+      { key: ctx.owners.owner1.publicKey, weight: new BN(60), label: "", expiresAt: null },
+      { key: ctx.owners.owner2.publicKey, weight: new BN(40), label: "", expiresAt: null },
     ];
     const threshold = new BN(100);
 
     await ctx.program.methods
-      .createWallet(owners, threshold)
+      .createWallet(owners, threshold, false, 0, false, new BN(0))
       .accounts({
         wallet: ctx.wallet.publicKey,
 
@@ -198,4 +203,148 @@ describe("power-multisig: create-wallet", () => {
     );
     expect(walletAccount.thresholdWeight.toNumber()).to.equal(100);
   });
+
+  it("fails when the total weight overflows u64", async () => {
+    const owners = [
+      { key: ctx.owners.owner1.publicKey, weight: new BN("18446744073709551615"), label: "", expiresAt: null },
+      { key: ctx.owners.owner2.publicKey, weight: new BN(1), label: "", expiresAt: null },
+    ];
+
+    try {
+      await ctx.program.methods
+        .createWallet(owners, new BN(1), false, 0, false, new BN(0))
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          payer: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.wallet, ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed with arithmetic overflow");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: ArithmeticOverflow");
+    }
+  });
+
+  it("creates wallet with total weight exactly u64::MAX", async () => {
+    const owners = [
+      { key: ctx.owners.owner1.publicKey, weight: new BN("18446744073709551614"), label: "", expiresAt: null },
+      { key: ctx.owners.owner2.publicKey, weight: new BN(1), label: "", expiresAt: null },
+    ];
+
+    await ctx.program.methods
+      .createWallet(owners, new BN("18446744073709551615"), false, 0, false, new BN(0))
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        payer: ctx.owners.owner1.publicKey,
+      })
+      .signers([ctx.wallet, ctx.owners.owner1])
+      .rpc();
+
+    const walletAccount = await ctx.program.account.wallet.fetch(
+      ctx.wallet.publicKey
+    );
+    expect(walletAccount.thresholdWeight.toString()).to.equal("18446744073709551615");
+  });
+
+  it("creates a zero-threshold wallet only when explicitly allowed", async () => {
+    const owners = [
+      { key: ctx.owners.owner1.publicKey, weight: 60 },
+      { key: ctx.owners.owner2.publicKey, weight: 30 },
+      { key: ctx.owners.owner3.publicKey, weight: 10 },
+    ];
+    await createMultisigWallet(ctx, owners, 0, true);
+
+    const walletAccount = await ctx.program.account.wallet.fetch(
+      ctx.wallet.publicKey
+    );
+    expect(walletAccount.thresholdWeight.toNumber()).to.equal(0);
+    expect(walletAccount.allowZeroThreshold).to.be.true;
+
+    // 最低权重的 owner3 单独即可执行
+    const receiver = anchor.web3.Keypair.generate();
+    await createAndExecuteProposal(
+      ctx,
+      SystemProgram.transfer({
+        fromPubkey: ctx.vault,
+        toPubkey: receiver.publicKey,
+        lamports: 0.1 * LAMPORTS_PER_SOL,
+      }),
+      [ctx.owners.owner3]
+    );
+
+    const balance = await ctx.provider.connection.getBalance(receiver.publicKey);
+    expect(balance).to.equal(0.1 * LAMPORTS_PER_SOL);
+  });
+
+  it("creates a single-owner wallet", async () => {
+    await createMultisigWallet(ctx, [{ key: ctx.owners.owner1.publicKey, weight: 1 }], 1);
+
+    const walletAccount = await ctx.program.account.wallet.fetch(
+      ctx.wallet.publicKey
+    );
+    expect(walletAccount.owners).to.have.length(1);
+    expect(walletAccount.minOwners).to.equal(0);
+  });
+
+  it("rejects a minimum owner count above the number of owners", async () => {
+    try {
+      await createMultisigWallet(ctx, undefined, 70, false, 4);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("TooFewOwners");
+    }
+  });
+
+  const walletOfSize = (count: number) =>
+    ctx.program.methods
+      .createWallet(
+        Array.from({ length: count }, () => ({
+          key: anchor.web3.Keypair.generate().publicKey,
+          weight: new BN(1),
+          label: "",
+          expiresAt: null,
+        })),
+        new BN(1),
+        false,
+        0,
+        false,
+        new BN(0)
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        payer: ctx.owners.owner1.publicKey,
+      })
+      .signers([ctx.wallet, ctx.owners.owner1])
+      .rpc();
+
+  it("accepts the maximum number of owners", async () => {
+    await walletOfSize(10);
+
+    const walletAccount = await ctx.program.account.wallet.fetch(
+      ctx.wallet.publicKey
+    );
+    expect(walletAccount.owners).to.have.length(10);
+  });
+
+  it("rejects more owners than a transaction can record approvals for", async () => {
+    try {
+      await walletOfSize(11);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("TooManyOwners");
+    }
+  });
+
+  it("records the creation time", async () => {
+    await createMultisigWallet(ctx);
+
+    const walletAccount = await ctx.program.account.wallet.fetch(
+      ctx.wallet.publicKey
+    );
+    const slot = await ctx.provider.connection.getSlot();
+    const blockTime = await ctx.provider.connection.getBlockTime(slot);
+
+    expect(walletAccount.createdAt.toNumber()).to.be.greaterThan(0);
+    expect(walletAccount.createdAt.toNumber()).to.be.at.most(blockTime);
+  });
 });
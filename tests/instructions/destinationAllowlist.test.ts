@@ -0,0 +1,172 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: destination-allowlist", () => {
+  let ctx: TestContext;
+  let allowlist: PublicKey;
+  let receiver: PublicKey;
+
+  const transferTo = (destination: PublicKey) =>
+    SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: destination,
+      lamports: 1_000_000,
+    });
+
+  const allowlistAccount = { isWritable: false, isSigner: false };
+
+  const propose = (ix: TransactionInstruction) =>
+    proposeTransaction(ctx, [ix], {
+      remainingAccounts: [{ pubkey: allowlist, ...allowlistAccount }],
+      approvers: [ctx.owners.owner2],
+    });
+
+  const execute = (proposal: PublicKey, ix: TransactionInstruction) =>
+    executeProposal(ctx, proposal, [ix], {
+      extraAccounts: [{ pubkey: allowlist, ...allowlistAccount }],
+    });
+
+  const addDestination = async (destination: PublicKey) =>
+    createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .addAllowedDestination(destination)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          allowlist,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    receiver = anchor.web3.Keypair.generate().publicKey;
+    [allowlist] = PublicKey.findProgramAddressSync(
+      [Buffer.from("allowlist"), ctx.wallet.publicKey.toBuffer()],
+      ctx.program.programId
+    );
+
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .createDestinationAllowlist()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          allowlist,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+    await addDestination(receiver);
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .setEnforceAllowlist(true)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+  });
+
+  it("pays out to an allowed destination", async () => {
+    const ix = transferTo(receiver);
+    const proposal = await propose(ix);
+    await execute(proposal, ix);
+
+    expect(await ctx.provider.connection.getBalance(receiver)).to.equal(1_000_000);
+    const account = await ctx.program.account.destinationAllowlist.fetch(allowlist);
+    expect(account.destinations.map(d => d.toBase58())).to.deep.equal([receiver.toBase58()]);
+  });
+
+  it("rejects proposals paying an unlisted destination", async () => {
+    try {
+      await propose(transferTo(anchor.web3.Keypair.generate().publicKey));
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("DestinationNotAllowed");
+    }
+  });
+
+  it("re-checks the allowlist when an in-flight proposal executes", async () => {
+    const ix = transferTo(receiver);
+    const proposal = await propose(ix);
+
+    // 提案创建后将收款方移出白名单
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .removeAllowedDestination(receiver)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          allowlist,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+    try {
+      await execute(proposal, ix);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("DestinationNotAllowed");
+    }
+    expect(await ctx.provider.connection.getBalance(receiver)).to.equal(0);
+  });
+
+  it("pays out to any destination once enforcement is turned off", async () => {
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .setEnforceAllowlist(false)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+    const destination = anchor.web3.Keypair.generate().publicKey;
+    const ix = transferTo(destination);
+    await execute(await propose(ix), ix);
+    expect(await ctx.provider.connection.getBalance(destination)).to.equal(1_000_000);
+  });
+
+  it("rejects adding a destination twice", async () => {
+    try {
+      await addDestination(receiver);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("DuplicateDestination");
+    }
+  });
+
+  it("cannot be changed without a proposal", async () => {
+    try {
+      await ctx.program.methods
+        .addAllowedDestination(ctx.owners.owner3.publicKey)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          allowlist,
+          vault: ctx.owners.owner3.publicKey,
+        })
+        .signers([ctx.owners.owner3])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -7,6 +7,7 @@ import {
   LAMPORTS_PER_SOL,
   Transaction,
 } from "@solana/web3.js";
+import { BN } from "bn.js";
 import { expect } from "chai";
 import { initializeContext, createMultisigWallet, TestContext } from "../helper";
 import { describe } from "mocha";
@@ -80,7 +81,7 @@ describe("execute_transaction", () => {
   
       try {
         await ctx.program.methods
-          .createTransaction([proposedIx])
+          .createTransaction([proposedIx], 3, 100, "", null, null, "")
           .accounts({
             wallet: ctx.wallet.publicKey,
             transaction: proposal.publicKey,
@@ -193,4 +194,151 @@ const allAccounts = [
       expect(transactionAccount.executed).to.be.true;
       expect(transactionAccount.signers).to.have.lengthOf(2);
     });
+
+    it("fails with insufficient signer weight", async () => {
+      const receiver = anchor.web3.Keypair.generate();
+      const proposal = anchor.web3.Keypair.generate();
+
+      const transferIx = SystemProgram.transfer({
+        fromPubkey: ctx.vault,
+        toPubkey: receiver.publicKey,
+        lamports: 0.1 * LAMPORTS_PER_SOL,
+      });
+
+      const proposedIx = {
+        programId: transferIx.programId,
+        accounts: transferIx.keys.map(key => ({
+          pubkey: key.pubkey,
+          isSigner: key.isSigner,
+          isWritable: key.isWritable
+        })),
+        data: Buffer.from(transferIx.data)
+      };
+
+      // owner1 (权重60) 创建提案, 低于阈值70
+      await ctx.program.methods
+        .createTransaction([proposedIx], 3, 100, "", null, null, "")
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposal.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .signers([proposal, ctx.owners.owner1])
+        .rpc();
+
+      try {
+        await ctx.program.methods
+          .executeTransaction()
+          .accounts({
+            transaction: proposal.publicKey,
+            owner: ctx.owners.owner1.publicKey,
+          })
+          .remainingAccounts([
+            { pubkey: ctx.vault, isWritable: true, isSigner: false },
+            { pubkey: receiver.publicKey, isWritable: true, isSigner: false },
+            { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+          ])
+          .signers([ctx.owners.owner1])
+          .rpc();
+        expect.fail("should have failed with insufficient signers weight");
+      } catch (error) {
+        expect(error.toString()).to.include("Error Code: InsufficientSigners");
+      }
+
+      const transactionAccount = await ctx.program.account.transaction.fetch(proposal.publicKey);
+      expect(transactionAccount.executed).to.be.false;
+    });
+
+    it("creates and executes in one transaction for a single-owner wallet", async () => {
+      // 单一所有者钱包: 创建者的权重在提案时即已计入
+      const soloWallet = anchor.web3.Keypair.generate();
+      const [soloVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), soloWallet.publicKey.toBuffer()],
+        ctx.program.programId
+      );
+
+      await ctx.program.methods
+        .createWallet(
+          [{ key: ctx.owners.owner1.publicKey, weight: new BN(1), label: "", expiresAt: null }],
+          new BN(1),
+          false,
+          0,
+          false,
+          new BN(0)
+        )
+        .accounts({
+          wallet: soloWallet.publicKey,
+          payer: ctx.owners.owner1.publicKey,
+        })
+        .signers([soloWallet, ctx.owners.owner1])
+        .rpc();
+
+      await ctx.provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: ctx.owners.owner1.publicKey,
+            toPubkey: soloVault,
+            lamports: LAMPORTS_PER_SOL,
+          })
+        ),
+        [ctx.owners.owner1]
+      );
+
+      const receiver = anchor.web3.Keypair.generate();
+      const proposal = anchor.web3.Keypair.generate();
+      const transferAmount = 0.1 * LAMPORTS_PER_SOL;
+      const transferIx = SystemProgram.transfer({
+        fromPubkey: soloVault,
+        toPubkey: receiver.publicKey,
+        lamports: transferAmount,
+      });
+
+      const createIx = await ctx.program.methods
+        .createTransaction(
+          [{
+            programId: transferIx.programId,
+            accounts: transferIx.keys.map(key => ({
+              pubkey: key.pubkey,
+              isSigner: key.isSigner,
+              isWritable: key.isWritable
+            })),
+            data: Buffer.from(transferIx.data)
+          }],
+          3,
+          100,
+          "",
+          null,
+          null,
+          ""
+        )
+        .accounts({
+          wallet: soloWallet.publicKey,
+          transaction: proposal.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .instruction();
+
+      const executeIx = await ctx.program.methods
+        .executeTransaction()
+        .accounts({
+          wallet: soloWallet.publicKey,
+          transaction: proposal.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: soloVault, isWritable: true, isSigner: false },
+          { pubkey: receiver.publicKey, isWritable: true, isSigner: false },
+          { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+        ])
+        .instruction();
+
+      await ctx.provider.sendAndConfirm(
+        new Transaction().add(createIx, executeIx),
+        [proposal, ctx.owners.owner1]
+      );
+
+      const transactionAccount = await ctx.program.account.transaction.fetch(proposal.publicKey);
+      expect(transactionAccount.executed).to.be.true;
+      expect(await ctx.provider.connection.getBalance(receiver.publicKey)).to.equal(transferAmount);
+    });
   });
\ No newline at end of file
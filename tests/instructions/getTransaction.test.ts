@@ -0,0 +1,134 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  createTransactionMethod,
+} from "./helper";
+
+describe("power-multisig: get-transaction", () => {
+  let ctx: TestContext;
+
+  const propose = async (): Promise<{ key: PublicKey; signature: string }> => {
+    const proposal = anchor.web3.Keypair.generate();
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner2.publicKey,
+      lamports: 1_000_000,
+    });
+
+    const signature = await createTransactionMethod(ctx, [ix], { proposal })
+      .rpc({ commitment: "confirmed" });
+
+    return { key: proposal.publicKey, signature };
+  };
+
+  const view = (transaction: PublicKey) =>
+    ctx.program.methods
+      .getTransaction()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction,
+      })
+      .view();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("records the owner_set_seqno across an owner change", async () => {
+    const before = await propose();
+
+    const changeWeightIx = await ctx.program.methods
+      .changeOwnerWeight(ctx.owners.owner3.publicKey, new BN(20))
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+    await createAndExecuteProposal(ctx, changeWeightIx);
+
+    const after = await propose();
+
+    const beforeInfo = await view(before.key);
+    expect(beforeInfo.ownerSetSeqno).to.equal(0);
+    expect(beforeInfo.walletOwnerSetSeqno).to.equal(1);
+    expect(beforeInfo.creator.equals(ctx.owners.owner1.publicKey)).to.be.true;
+
+    const afterInfo = await view(after.key);
+    expect(afterInfo.ownerSetSeqno).to.equal(1);
+    expect(afterInfo.walletOwnerSetSeqno).to.equal(1);
+  });
+
+  it("emits the owner_set_seqno in TransactionCreated", async () => {
+    const { key, signature } = await propose();
+
+    const tx = await ctx.provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(ctx.program.programId, ctx.program.coder);
+    const events = [...parser.parseLogs(tx.meta.logMessages)];
+
+    const created = events.find(e => e.name === "transactionCreated");
+    expect(created).to.not.be.undefined;
+    expect(created.data.transaction.equals(key)).to.be.true;
+    expect(created.data.ownerSetSeqno).to.equal(0);
+    expect(created.data.title).to.equal("");
+  });
+
+  it("records the owner whose signature crossed the threshold", async () => {
+    const { key } = await propose();
+    expect((await view(key)).finalSigner).to.be.null;
+
+    const approve = (owner: anchor.web3.Keypair) =>
+      ctx.program.methods
+        .approve()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: key,
+          owner: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+
+    // owner1(60) + owner3(10) 刚好达到阈值70, 之后 owner2 的签名不再改变记录
+    await approve(ctx.owners.owner3);
+    await approve(ctx.owners.owner2);
+
+    const info = await view(key);
+    expect(info.finalSigner.equals(ctx.owners.owner3.publicKey)).to.be.true;
+  });
+
+  it("returns the instructions as execute_transaction will invoke them", async () => {
+    const { key } = await propose();
+    const stored = await ctx.program.account.transaction.fetch(key);
+
+    const instructions = await ctx.program.methods
+      .getTransactionInstructions()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: key,
+        vault: ctx.vault,
+      })
+      .view();
+
+    expect(instructions).to.have.length(1);
+    const [ix] = instructions;
+    expect(ix.programId.equals(SystemProgram.programId)).to.be.true;
+    expect(Buffer.from(ix.data).equals(Buffer.from(stored.instructions[0].data))).to.be.true;
+    expect(ix.accounts.map(a => a.pubkey.toBase58())).to.deep.equal(
+      stored.instructions[0].accounts.map(a => a.pubkey.toBase58())
+    );
+
+    // vault 作为签名者和可写账户传入
+    expect(ix.accounts[0].pubkey.equals(ctx.vault)).to.be.true;
+    expect(ix.accounts[0].isSigner).to.be.true;
+    expect(ix.accounts[0].isWritable).to.be.true;
+  });
+});
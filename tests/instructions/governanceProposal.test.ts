@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+// 一个提案中包含多个治理操作, 与普通转账走同一审批流程
+describe("power-multisig: governance-proposal", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  let instructions: TransactionInstruction[];
+  const newOwner = anchor.web3.Keypair.generate();
+
+  const propose = async (threshold: number) => {
+    instructions = [
+      await ctx.program.methods
+        .addOwner({ key: newOwner.publicKey, weight: new BN(40), label: "", expiresAt: null })
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction(),
+      await ctx.program.methods
+        .changeThreshold(new BN(threshold))
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction(),
+    ];
+
+    proposalKey = await proposeTransaction(ctx, instructions);
+  };
+
+  const approve = (owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  const execute = () => executeProposal(ctx, proposalKey, instructions);
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("requires quorum before applying the changes", async () => {
+    await propose(100);
+    try {
+      await execute();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InsufficientSigners");
+    }
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners.length).to.equal(3);
+  });
+
+  it("applies every change once approved", async () => {
+    await propose(100);
+    await approve(ctx.owners.owner2);
+    await execute();
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners.some(o => o.key.equals(newOwner.publicKey))).to.be.true;
+    expect(wallet.thresholdWeight.toNumber()).to.equal(100);
+    expect(wallet.ownerSetSeqno).to.equal(2);
+  });
+
+  it("applies nothing when the resulting configuration is invalid", async () => {
+    // 新总权重为140, 阈值150无法达到
+    await propose(150);
+    await approve(ctx.owners.owner2);
+    try {
+      await execute();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("ThresholdTooHigh");
+    }
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners.length).to.equal(3);
+    expect(wallet.thresholdWeight.toNumber()).to.equal(70);
+  });
+});
@@ -0,0 +1,182 @@
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL, TransactionInstruction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+} from "./helper";
+
+// SPL Memo 程序, 本地验证器默认加载
+const MEMO_PROGRAM_ID = new PublicKey("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+describe("power-multisig: has-executable-transaction", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+
+  const hasExecutable = (transactions: PublicKey[]) =>
+    ctx.program.methods
+      .hasExecutableTransaction()
+      .accounts({ wallet: ctx.wallet.publicKey })
+      .remainingAccounts(
+        transactions.map(pubkey => ({ pubkey, isWritable: false, isSigner: false }))
+      )
+      .view();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    // owner1(60) 提案, 尚未达到阈值70
+    proposalKey = await proposeTransaction(ctx, [
+      SystemProgram.transfer({
+        fromPubkey: ctx.vault,
+        toPubkey: ctx.owners.owner3.publicKey,
+        lamports: 0.1 * LAMPORTS_PER_SOL,
+      }),
+    ]);
+  });
+
+  it("returns false when no transactions are passed", async () => {
+    expect(await hasExecutable([])).to.be.false;
+  });
+
+  it("returns false when pending transactions lack weight", async () => {
+    expect(await hasExecutable([proposalKey])).to.be.false;
+  });
+
+  it("returns true once a transaction reaches the threshold", async () => {
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+
+    expect(await hasExecutable([proposalKey])).to.be.true;
+  });
+
+  describe("get_executable_transactions", () => {
+    const propose = (dependsOn: PublicKey | null) =>
+      proposeTransaction(
+        ctx,
+        [
+          SystemProgram.transfer({
+            fromPubkey: ctx.vault,
+            toPubkey: ctx.owners.owner2.publicKey,
+            lamports: 0.1 * LAMPORTS_PER_SOL,
+          }),
+        ],
+        {
+          dependsOn,
+          remainingAccounts: dependsOn ? [{ pubkey: dependsOn, isWritable: false, isSigner: false }] : [],
+          approvers: [ctx.owners.owner2],
+        }
+      );
+
+    const executable = (transactions: PublicKey[], start: number, limit: number) =>
+      ctx.program.methods
+        .getExecutableTransactions(start, limit)
+        .accounts({ wallet: ctx.wallet.publicKey })
+        .remainingAccounts(
+          transactions.map(pubkey => ({ pubkey, isWritable: false, isSigner: false }))
+        )
+        .view();
+
+    it("filters out transactions that fail any execution check", async () => {
+      // proposalKey 权重不足; ready 可执行; dependent 依赖未执行的 proposalKey
+      const ready = await propose(null);
+      const dependent = await propose(proposalKey);
+      const all = [proposalKey, ready, dependent];
+
+      const result = await executable(all, 0, all.length);
+      expect(result.map(k => k.toBase58())).to.deep.equal([ready.toBase58()]);
+
+      // 分页只检查指定范围
+      expect(await executable(all, 2, 1)).to.deep.equal([]);
+      expect((await executable(all, 1, 1)).map(k => k.toBase58())).to.deep.equal([ready.toBase58()]);
+    });
+
+    it("filters out transactions calling a program that is no longer allowed", async () => {
+      const memo = await proposeTransaction(
+        ctx,
+        [
+          new TransactionInstruction({
+            programId: MEMO_PROGRAM_ID,
+            keys: [{ pubkey: ctx.vault, isSigner: true, isWritable: false }],
+            data: Buffer.from("allowlist", "utf8"),
+          }),
+        ],
+        { approvers: [ctx.owners.owner2] }
+      );
+      expect((await executable([memo], 0, 1)).map(k => k.toBase58())).to.deep.equal([memo.toBase58()]);
+
+      // 提案通过后才限制程序, 执行时会报 ProgramNotAllowed
+      await createAndExecuteProposal(
+        ctx,
+        await ctx.program.methods
+          .addAllowedProgram(SystemProgram.programId)
+          .accounts({
+            wallet: ctx.wallet.publicKey,
+            vault: ctx.vault,
+          })
+          .instruction()
+      );
+
+      expect(await executable([memo], 0, 1)).to.deep.equal([]);
+      expect(await hasExecutable([memo])).to.be.false;
+    });
+
+    it("filters out transactions paying a destination that is not allowed", async () => {
+      await ctx.program.methods
+        .approve()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposalKey,
+          owner: ctx.owners.owner2.publicKey,
+        })
+        .signers([ctx.owners.owner2])
+        .rpc();
+
+      const [allowlist] = PublicKey.findProgramAddressSync(
+        [Buffer.from("allowlist"), ctx.wallet.publicKey.toBuffer()],
+        ctx.program.programId
+      );
+
+      // 提案通过后才启用空白名单, 收款方 owner3 不在其中
+      await createAndExecuteProposal(
+        ctx,
+        await ctx.program.methods
+          .createDestinationAllowlist()
+          .accounts({ wallet: ctx.wallet.publicKey, allowlist, vault: ctx.vault })
+          .instruction()
+      );
+      await createAndExecuteProposal(
+        ctx,
+        await ctx.program.methods
+          .setEnforceAllowlist(true)
+          .accounts({ wallet: ctx.wallet.publicKey, vault: ctx.vault })
+          .instruction()
+      );
+
+      expect(await executable([proposalKey, allowlist], 0, 2)).to.deep.equal([]);
+      expect(await hasExecutable([proposalKey, allowlist])).to.be.false;
+
+      // 加入白名单后重新可执行
+      await createAndExecuteProposal(
+        ctx,
+        await ctx.program.methods
+          .addAllowedDestination(ctx.owners.owner3.publicKey)
+          .accounts({ wallet: ctx.wallet.publicKey, allowlist, vault: ctx.vault })
+          .instruction()
+      );
+      expect((await executable([proposalKey, allowlist], 0, 2)).map(k => k.toBase58())).to.deep.equal([
+        proposalKey.toBase58(),
+      ]);
+    });
+  });
+});
@@ -6,6 +6,7 @@ import {
   SystemProgram, 
   LAMPORTS_PER_SOL,
   TransactionInstruction,
+  AccountMeta,
   Transaction,
   sendAndConfirmTransaction
 } from "@solana/web3.js";
@@ -74,17 +75,30 @@ export async function initializeContext(): Promise<TestContext> {
 // 创建钱包辅助函数
 export async function createMultisigWallet(
   ctx: TestContext,
-  owners: { key: PublicKey; weight: number }[] = [
+  owners: { key: PublicKey; weight: number; expiresAt?: number }[] = [
     { key: ctx.owners.owner1.publicKey, weight: 60 },
     { key: ctx.owners.owner2.publicKey, weight: 30 },
     { key: ctx.owners.owner3.publicKey, weight: 10 },
   ],
-  threshold: number = 70
+  threshold: number = 70,
+  allowZeroThreshold: boolean = false,
+  minOwners: number = 0,
+  autoExecute: boolean = false,
+  executionDelay: number = 0
 ) {
   await ctx.program.methods
     .createWallet(
-      owners.map(o => ({ key: o.key, weight: new BN(o.weight) })),
-      new BN(threshold)
+      owners.map(o => ({
+        key: o.key,
+        weight: new BN(o.weight),
+        label: "",
+        expiresAt: o.expiresAt === undefined ? null : new BN(o.expiresAt),
+      })),
+      new BN(threshold),
+      allowZeroThreshold,
+      minOwners,
+      autoExecute,
+      new BN(executionDelay)
     )
     .accounts({
       wallet: ctx.wallet.publicKey,
@@ -106,16 +120,9 @@ export async function createMultisigWallet(
   );
 }
 
-// 创建并执行提案的辅助函数
-export async function createAndExecuteProposal(
-  ctx: TestContext,
-  instruction: TransactionInstruction,
-  signers: anchor.web3.Keypair[] = [ctx.owners.owner1, ctx.owners.owner2]
-) {
-  const proposal = anchor.web3.Keypair.generate();
-  
-  // 转换为 ProposedInstruction 格式
-  const proposedIx = {
+// 将指令转换为 ProposedInstruction 格式
+export function toProposedInstruction(instruction: TransactionInstruction) {
+  return {
     programId: instruction.programId,
     accounts: instruction.keys.map(key => ({
       pubkey: key.pubkey,
@@ -124,48 +131,116 @@ export async function createAndExecuteProposal(
     })),
     data: Buffer.from(instruction.data)
   };
+}
 
-  // 创建提案
-  await ctx.program.methods
-    .createTransaction([proposedIx])
+// 创建提案的可选参数
+export type ProposeOptions = {
+  proposal?: anchor.web3.Keypair;
+  creator?: anchor.web3.Keypair;
+  title?: string;
+  dependsOn?: PublicKey | null;
+  eta?: number | null;
+  memo?: string;
+  maxAccounts?: number;
+  maxDataSize?: number;
+  remainingAccounts?: AccountMeta[];
+};
+
+// 构造 createTransaction 调用, 便于测试自行决定如何发送
+export function createTransactionMethod(
+  ctx: TestContext,
+  instructions: TransactionInstruction[],
+  options: ProposeOptions = {}
+) {
+  const proposal = options.proposal ?? anchor.web3.Keypair.generate();
+  const creator = options.creator ?? ctx.owners.owner1;
+  return ctx.program.methods
+    .createTransaction(
+      instructions.map(toProposedInstruction),
+      options.maxAccounts ?? Math.max(...instructions.map(ix => ix.keys.length)),
+      options.maxDataSize ?? Math.max(...instructions.map(ix => ix.data.length)),
+      options.title ?? "",
+      options.dependsOn ?? null,
+      options.eta == null ? null : new BN(options.eta),
+      options.memo ?? ""
+    )
     .accounts({
       wallet: ctx.wallet.publicKey,
       transaction: proposal.publicKey,
-      owner: signers[0].publicKey,
+      owner: creator.publicKey,
     })
-    .signers([proposal, signers[0]])
-    .rpc();
+    .remainingAccounts(options.remainingAccounts ?? [])
+    .signers([proposal, creator]);
+}
+
+// 创建提案并让 approvers 依次批准, 返回提案地址
+export async function proposeTransaction(
+  ctx: TestContext,
+  instructions: TransactionInstruction[],
+  options: ProposeOptions & { approvers?: anchor.web3.Keypair[] } = {}
+): Promise<PublicKey> {
+  const proposal = options.proposal ?? anchor.web3.Keypair.generate();
+  await createTransactionMethod(ctx, instructions, { ...options, proposal }).rpc();
 
-  // 其他签名者审批
-  for (const signer of signers.slice(1)) {
+  for (const approver of options.approvers ?? []) {
     await ctx.program.methods
       .approve()
       .accounts({
         wallet: ctx.wallet.publicKey,
         transaction: proposal.publicKey,
-        owner: signer.publicKey,
+        owner: approver.publicKey,
       })
-      .signers([signer])
+      .signers([approver])
       .rpc();
   }
 
-  // 执行提案
-  await ctx.program.methods
+  return proposal.publicKey;
+}
+
+// 执行提案: 合并所有指令涉及的账户和程序并去重, vault 由程序通过 invoke_signed 签名
+export function executeProposal(
+  ctx: TestContext,
+  proposal: PublicKey,
+  instructions: TransactionInstruction[],
+  options: { executor?: anchor.web3.Keypair; extraAccounts?: AccountMeta[] } = {}
+) {
+  const executor = options.executor ?? ctx.owners.owner1;
+  const accounts = new Map<string, AccountMeta>();
+  for (const ix of instructions) {
+    for (const key of [...ix.keys, { pubkey: ix.programId, isSigner: false, isWritable: false }]) {
+      const existing = accounts.get(key.pubkey.toBase58());
+      accounts.set(key.pubkey.toBase58(), {
+        pubkey: key.pubkey,
+        isSigner: false,
+        isWritable: key.isWritable || (existing?.isWritable ?? false),
+      });
+    }
+  }
+
+  return ctx.program.methods
     .executeTransaction()
     .accounts({
-      transaction: proposal.publicKey,
-      owner: signers[0].publicKey,
+      transaction: proposal,
+      owner: executor.publicKey,
     })
-    .remainingAccounts([
-      ...instruction.keys,
-      {
-        pubkey: instruction.programId,
-        isWritable: false,
-        isSigner: false,
-      }
-    ])
-    .signers([signers[0]])
+    .remainingAccounts([...accounts.values(), ...(options.extraAccounts ?? [])])
+    .signers([executor])
     .rpc();
+}
+
+// 创建并执行提案的辅助函数
+export async function createAndExecuteProposal(
+  ctx: TestContext,
+  instruction: TransactionInstruction,
+  signers: anchor.web3.Keypair[] = [ctx.owners.owner1, ctx.owners.owner2]
+) {
+  const proposal = anchor.web3.Keypair.generate();
+  await proposeTransaction(ctx, [instruction], {
+    proposal,
+    creator: signers[0],
+    approvers: signers.slice(1),
+  });
+  await executeProposal(ctx, proposal.publicKey, [instruction], { executor: signers[0] });
 
   return proposal;
 }
@@ -177,44 +252,9 @@ export async function expectProposalToFail(
   expectedError: string,
   signer = ctx.owners.owner1
 ) {
-  const proposal = anchor.web3.Keypair.generate();
-  const proposedIx = {
-    programId: instruction.programId,
-    accounts: instruction.keys.map(key => ({
-      pubkey: key.pubkey,
-      isSigner: key.isSigner,
-      isWritable: key.isWritable
-    })),
-    data: Buffer.from(instruction.data)
-  };
-
   try {
-    await ctx.program.methods
-      .createTransaction([proposedIx])
-      .accounts({
-        wallet: ctx.wallet.publicKey,
-        transaction: proposal.publicKey,
-        owner: signer.publicKey,
-      })
-      .signers([proposal, signer])
-      .rpc();
-    
-    await ctx.program.methods
-      .executeTransaction()
-      .accounts({
-        transaction: proposal.publicKey,
-        owner: signer.publicKey,
-      })
-      .remainingAccounts([
-        ...instruction.keys,
-        {
-          pubkey: instruction.programId,
-          isWritable: false,
-          isSigner: false,
-        }
-      ])
-      .signers([signer])
-      .rpc();
+    const proposal = await proposeTransaction(ctx, [instruction], { creator: signer });
+    await executeProposal(ctx, proposal, [instruction], { executor: signer });
 
     throw new Error("Transaction should have failed");
   } catch (error) {
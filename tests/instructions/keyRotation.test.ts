@@ -0,0 +1,130 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: key-rotation", () => {
+  let ctx: TestContext;
+  let newKey: anchor.web3.Keypair;
+
+  const proposeRotation = async (oldOwner: PublicKey, newOwner: PublicKey) =>
+    createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .proposeKeyRotation(oldOwner, newOwner)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+  const accept = (signer: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .acceptKeyRotation()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        newOwner: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+
+  const cancel = (signer: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .cancelKeyRotation()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    newKey = anchor.web3.Keypair.generate();
+    await proposeRotation(ctx.owners.owner3.publicKey, newKey.publicKey);
+  });
+
+  it("keeps the old key until the new key accepts", async () => {
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.pendingKeyRotation.oldOwner.equals(ctx.owners.owner3.publicKey)).to.be.true;
+    expect(wallet.pendingKeyRotation.newOwner.equals(newKey.publicKey)).to.be.true;
+    expect(wallet.owners.some(o => o.key.equals(ctx.owners.owner3.publicKey))).to.be.true;
+    expect(wallet.ownerSetSeqno).to.equal(0);
+  });
+
+  it("swaps the key once the new key accepts", async () => {
+    await accept(newKey);
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    const seat = wallet.owners.find(o => o.key.equals(newKey.publicKey));
+    expect(seat.weight.toNumber()).to.equal(10);
+    expect(wallet.owners.some(o => o.key.equals(ctx.owners.owner3.publicKey))).to.be.false;
+    expect(wallet.pendingKeyRotation).to.be.null;
+    // 旧密钥在待处理交易上的批准随序号递增失效
+    expect(wallet.ownerSetSeqno).to.equal(1);
+  });
+
+  it("rejects acceptance by any other key", async () => {
+    try {
+      await accept(ctx.owners.owner3);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnauthorizedKeyRotation");
+    }
+  });
+
+  it("rejects a second rotation while one is pending", async () => {
+    try {
+      await proposeRotation(ctx.owners.owner2.publicKey, anchor.web3.Keypair.generate().publicKey);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("KeyRotationPending");
+    }
+  });
+
+  it("lets the old key cancel the rotation", async () => {
+    await cancel(ctx.owners.owner3);
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.pendingKeyRotation).to.be.null;
+
+    try {
+      await accept(newKey);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("NoKeyRotation");
+    }
+  });
+
+  it("lets the multisig cancel the rotation", async () => {
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .cancelKeyRotation()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          authority: ctx.vault,
+        })
+        .instruction()
+    );
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.pendingKeyRotation).to.be.null;
+  });
+
+  it("rejects cancellation by another owner", async () => {
+    try {
+      await cancel(ctx.owners.owner2);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnauthorizedKeyRotation");
+    }
+  });
+});
@@ -0,0 +1,77 @@
+import { SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+} from "./helper";
+
+describe("power-multisig: max-transactions", () => {
+  let ctx: TestContext;
+
+  const transferIx = () =>
+    SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+
+  const setMaxIx = (max: number) =>
+    ctx.program.methods
+      .setMaxTransactions(new BN(max))
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("counts every proposal without a cap by default", async () => {
+    await proposeTransaction(ctx, [transferIx()]);
+    await proposeTransaction(ctx, [transferIx()]);
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.transactionCount.toNumber()).to.equal(2);
+    expect(wallet.maxTransactions.toNumber()).to.equal(0);
+  });
+
+  it("rejects new proposals once the cap is reached", async () => {
+    // 设置上限的提案本身是第1笔, 之后只能再创建1笔
+    await createAndExecuteProposal(ctx, await setMaxIx(2));
+    await proposeTransaction(ctx, [transferIx()]);
+
+    try {
+      await proposeTransaction(ctx, [transferIx()]);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("TransactionLimitReached");
+    }
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.transactionCount.toNumber()).to.equal(2);
+    expect(wallet.maxTransactions.toNumber()).to.equal(2);
+  });
+
+  it("cannot be changed without a proposal", async () => {
+    try {
+      await ctx.program.methods
+        .setMaxTransactions(new BN(1))
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -0,0 +1,41 @@
+import { SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+} from "./helper";
+
+describe("power-multisig: migrate-wallet", () => {
+  let ctx: TestContext;
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("rejects a wallet that already uses the current layout", async () => {
+    const sizeBefore = (await ctx.provider.connection.getAccountInfo(ctx.wallet.publicKey)).data.length;
+
+    try {
+      await ctx.program.methods
+        .migrateWallet()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("NotLegacyWallet");
+    }
+
+    // 账户保持原样
+    const sizeAfter = (await ctx.provider.connection.getAccountInfo(ctx.wallet.publicKey)).data.length;
+    expect(sizeAfter).to.equal(sizeBefore);
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners).to.have.length(3);
+  });
+});
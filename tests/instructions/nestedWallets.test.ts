@@ -0,0 +1,231 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  Transaction,
+} from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: nested-wallets", () => {
+  let ctx: TestContext;
+  let childWallet: anchor.web3.Keypair;
+  let childVault: PublicKey;
+
+  beforeEach(async () => {
+    // 父钱包: owner1(60) owner2(30) owner3(10), 阈值70
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    // 子钱包: 父钱包的 vault(60) + owner3(40), 阈值60
+    childWallet = anchor.web3.Keypair.generate();
+    [childVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), childWallet.publicKey.toBuffer()],
+      ctx.program.programId
+    );
+
+    await ctx.program.methods
+      .createWallet(
+        [
+          { key: ctx.vault, weight: new BN(60), label: "", expiresAt: null },
+          { key: ctx.owners.owner3.publicKey, weight: new BN(40), label: "", expiresAt: null },
+        ],
+        new BN(60),
+        false,
+        0,
+        false,
+        new BN(0)
+      )
+      .accounts({
+        wallet: childWallet.publicKey,
+        payer: ctx.owners.owner1.publicKey,
+      })
+      .signers([childWallet, ctx.owners.owner1])
+      .rpc();
+
+    await ctx.provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: ctx.owners.owner1.publicKey,
+          toPubkey: childVault,
+          lamports: LAMPORTS_PER_SOL,
+        })
+      ),
+      [ctx.owners.owner1]
+    );
+  });
+
+  it("counts the parent wallet's approval toward the child's quorum", async () => {
+    const receiver = anchor.web3.Keypair.generate();
+    const transferAmount = 0.2 * LAMPORTS_PER_SOL;
+    const transferIx = SystemProgram.transfer({
+      fromPubkey: childVault,
+      toPubkey: receiver.publicKey,
+      lamports: transferAmount,
+    });
+
+    // owner3 在子钱包中提案, 权重40不足阈值60
+    const childTx = anchor.web3.Keypair.generate();
+    await ctx.program.methods
+      .createTransaction(
+        [
+          {
+            programId: transferIx.programId,
+            accounts: transferIx.keys.map(key => ({
+              pubkey: key.pubkey,
+              isSigner: key.isSigner,
+              isWritable: key.isWritable,
+            })),
+            data: Buffer.from(transferIx.data),
+          },
+        ],
+        transferIx.keys.length,
+        transferIx.data.length,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: childWallet.publicKey,
+        transaction: childTx.publicKey,
+        owner: ctx.owners.owner3.publicKey,
+      })
+      .signers([childTx, ctx.owners.owner3])
+      .rpc();
+
+    // 父钱包通过提案以 vault 身份批准子钱包交易
+    const approveIx = await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: childWallet.publicKey,
+        transaction: childTx.publicKey,
+        owner: ctx.vault,
+      })
+      .instruction();
+    await createAndExecuteProposal(ctx, approveIx);
+
+    const childTxAccount = await ctx.program.account.transaction.fetch(childTx.publicKey);
+    expect(childTxAccount.signers).to.have.length(2);
+    expect(childTxAccount.signers[1].equals(ctx.vault)).to.be.true;
+
+    // 父钱包的权重已计入, owner3 可以执行
+    await ctx.program.methods
+      .executeTransaction()
+      .accounts({
+        wallet: childWallet.publicKey,
+        transaction: childTx.publicKey,
+        owner: ctx.owners.owner3.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: childVault, isWritable: true, isSigner: false },
+        { pubkey: receiver.publicKey, isWritable: true, isSigner: false },
+        { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+      ])
+      .signers([ctx.owners.owner3])
+      .rpc();
+
+    const balance = await ctx.provider.connection.getBalance(receiver.publicKey);
+    expect(balance).to.equal(transferAmount);
+  });
+
+  it("rejects a parent approval that has not reached the parent's quorum", async () => {
+    const childTx = anchor.web3.Keypair.generate();
+    const transferIx = SystemProgram.transfer({
+      fromPubkey: childVault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1,
+    });
+    await ctx.program.methods
+      .createTransaction(
+        [
+          {
+            programId: transferIx.programId,
+            accounts: transferIx.keys.map(key => ({
+              pubkey: key.pubkey,
+              isSigner: key.isSigner,
+              isWritable: key.isWritable,
+            })),
+            data: Buffer.from(transferIx.data),
+          },
+        ],
+        transferIx.keys.length,
+        transferIx.data.length,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: childWallet.publicKey,
+        transaction: childTx.publicKey,
+        owner: ctx.owners.owner3.publicKey,
+      })
+      .signers([childTx, ctx.owners.owner3])
+      .rpc();
+
+    const approveIx = await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: childWallet.publicKey,
+        transaction: childTx.publicKey,
+        owner: ctx.vault,
+      })
+      .instruction();
+
+    // 父钱包只有 owner1(60) 签名, 低于阈值70
+    try {
+      await createAndExecuteProposal(ctx, approveIx, [ctx.owners.owner1]);
+      expect.fail("should have failed with insufficient signers weight");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: InsufficientSigners");
+    }
+
+    const childTxAccount = await ctx.program.account.transaction.fetch(childTx.publicKey);
+    expect(childTxAccount.signers).to.have.length(1);
+  });
+
+  it("detects owners holding weight both directly and through a nested wallet", async () => {
+    const unrelated = anchor.web3.Keypair.generate();
+    await ctx.program.methods
+      .createWallet(
+        [{ key: ctx.owners.owner3.publicKey, weight: new BN(1), label: "", expiresAt: null }],
+        new BN(1),
+        false,
+        0,
+        false,
+        new BN(0)
+      )
+      .accounts({
+        wallet: unrelated.publicKey,
+        payer: ctx.owners.owner1.publicKey,
+      })
+      .signers([unrelated, ctx.owners.owner1])
+      .rpc();
+
+    const detect = (wallets: PublicKey[]) =>
+      ctx.program.methods
+        .detectNestedDoubleCounting()
+        .accounts({ wallet: childWallet.publicKey })
+        .remainingAccounts(
+          wallets.map(pubkey => ({ pubkey, isWritable: false, isSigner: false }))
+        )
+        .view();
+
+    // owner3 直接持有子钱包权重, 同时也是父钱包的所有者
+    const overlapping = await detect([ctx.wallet.publicKey]);
+    expect(overlapping.map(k => k.toBase58())).to.deep.equal([
+      ctx.owners.owner3.publicKey.toBase58(),
+    ]);
+
+    // 不是子钱包所有者的钱包不参与统计
+    expect(await detect([unrelated.publicKey])).to.deep.equal([]);
+  });
+});
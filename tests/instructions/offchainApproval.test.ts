@@ -0,0 +1,156 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Ed25519Program,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+} from "./helper";
+
+describe("power-multisig: offchain-approval", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+
+  const approvalAddress = (owner: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("offchain-approval"), proposalKey.toBuffer(), owner.toBuffer()],
+      ctx.program.programId
+    )[0];
+
+  // 由中继者提交某个所有者离线签名的批准
+  const submitApproval = async (owner: anchor.web3.Keypair, message: Uint8Array) => {
+    await ctx.program.methods
+      .verifyOffchainApproval(owner.publicKey)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        approval: approvalAddress(owner.publicKey),
+        relayer: ctx.provider.wallet.publicKey,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: owner.secretKey,
+          message,
+        }),
+      ])
+      .rpc();
+  };
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    const proposal = anchor.web3.Keypair.generate();
+    proposalKey = proposal.publicKey;
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    await ctx.program.methods
+      .createTransaction(
+        [{
+          programId: ix.programId,
+          accounts: ix.keys.map(key => ({
+            pubkey: key.pubkey,
+            isSigner: key.isSigner,
+            isWritable: key.isWritable,
+          })),
+          data: Buffer.from(ix.data),
+        }],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([proposal, ctx.owners.owner1])
+      .rpc();
+  });
+
+  const approvalDigest = async () =>
+    Uint8Array.from(
+      await ctx.program.methods
+        .getApprovalDigest()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposalKey,
+        })
+        .view()
+    );
+
+  it("records an approval signed off-chain", async () => {
+    await submitApproval(ctx.owners.owner2, await approvalDigest());
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.signers.some(s => s.equals(ctx.owners.owner2.publicKey))).to.be.true;
+
+    const approval = await ctx.program.account.offchainApproval.fetch(
+      approvalAddress(ctx.owners.owner2.publicKey)
+    );
+    expect(approval.transaction.equals(proposalKey)).to.be.true;
+    expect(approval.owner.equals(ctx.owners.owner2.publicKey)).to.be.true;
+  });
+
+  it("rejects a signature over a different message", async () => {
+    const digest = await approvalDigest();
+    digest[0] ^= 0xff;
+
+    try {
+      await submitApproval(ctx.owners.owner2, digest);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidOffchainApproval");
+    }
+  });
+
+  it("rejects a signature from another key", async () => {
+    const outsider = anchor.web3.Keypair.generate();
+    try {
+      await ctx.program.methods
+        .verifyOffchainApproval(ctx.owners.owner2.publicKey)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposalKey,
+          approval: approvalAddress(ctx.owners.owner2.publicKey),
+          relayer: ctx.provider.wallet.publicKey,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: outsider.secretKey,
+            message: await approvalDigest(),
+          }),
+        ])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidOffchainApproval");
+    }
+  });
+
+  it("rejects replaying the same approval", async () => {
+    const digest = await approvalDigest();
+    await submitApproval(ctx.owners.owner2, digest);
+
+    try {
+      await submitApproval(ctx.owners.owner2, digest);
+      expect.fail("Should have failed");
+    } catch (error) {
+      // 标记账户已存在，无法再次创建
+      expect(error.logs.join("\n")).to.include("already in use");
+    }
+  });
+});
@@ -0,0 +1,88 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+} from "./helper";
+
+describe("power-multisig: owner-label", () => {
+  let ctx: TestContext;
+
+  const setLabel = (owner: anchor.web3.Keypair, label: string) =>
+    ctx.program.methods
+      .setOwnerLabel(owner.publicKey, label)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("lets an owner label their own entry", async () => {
+    await setLabel(ctx.owners.owner2, "ops-alice");
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    const owner2 = wallet.owners.find(o => o.key.equals(ctx.owners.owner2.publicKey));
+    expect(owner2.label).to.equal("ops-alice");
+    // 标签不影响权重与序号
+    expect(owner2.weight.toNumber()).to.equal(30);
+    expect(wallet.ownerSetSeqno).to.equal(0);
+  });
+
+  it("rejects labeling another owner's entry", async () => {
+    try {
+      await ctx.program.methods
+        .setOwnerLabel(ctx.owners.owner1.publicKey, "not-mine")
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          owner: ctx.owners.owner2.publicKey,
+        })
+        .signers([ctx.owners.owner2])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("NotOwner");
+    }
+  });
+
+  it("rejects labels longer than the limit", async () => {
+    try {
+      await setLabel(ctx.owners.owner2, "x".repeat(33));
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidNameLength");
+    }
+  });
+
+  it("accepts labels when creating a wallet", async () => {
+    const wallet = anchor.web3.Keypair.generate();
+    await ctx.program.methods
+      .createWallet(
+        [
+          { key: ctx.owners.owner1.publicKey, weight: new BN(60), label: "treasury-lead", expiresAt: null },
+          { key: ctx.owners.owner2.publicKey, weight: new BN(40), label: "x".repeat(32), expiresAt: null },
+        ],
+        new BN(60),
+        false,
+        0,
+        false,
+        new BN(0)
+      )
+      .accounts({
+        wallet: wallet.publicKey,
+        payer: ctx.owners.owner1.publicKey,
+      })
+      .signers([wallet, ctx.owners.owner1])
+      .rpc();
+
+    const account = await ctx.program.account.wallet.fetch(wallet.publicKey);
+    expect(account.owners.map(o => o.label)).to.deep.equal(["treasury-lead", "x".repeat(32)]);
+  });
+});
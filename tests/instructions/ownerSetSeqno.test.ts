@@ -0,0 +1,72 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: owner-set-seqno", () => {
+  let ctx: TestContext;
+  let transferIx: TransactionInstruction;
+
+  const propose = () => proposeTransaction(ctx, [transferIx]);
+
+  const approve = (transaction: PublicKey, owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+  });
+
+  it("rejects signing and execution after the owner set changes", async () => {
+    // approved 已达到阈值, pending 只有提案者签名
+    const approved = await propose();
+    await approve(approved, ctx.owners.owner2);
+    const pending = await propose();
+
+    const addOwnerIx = await ctx.program.methods
+      .addOwner({ key: anchor.web3.Keypair.generate().publicKey, weight: new BN(10), label: "", expiresAt: null })
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    await createAndExecuteProposal(ctx, addOwnerIx);
+
+    try {
+      await approve(pending, ctx.owners.owner2);
+      expect.fail("approve should have failed with owner set changed");
+    } catch (error) {
+      expect(error.toString()).to.include("OwnerSetChanged");
+    }
+
+    try {
+      await executeProposal(ctx, approved, [transferIx]);
+      expect.fail("execute should have failed with owner set changed");
+    } catch (error) {
+      expect(error.toString()).to.include("OwnerSetChanged");
+    }
+  });
+});
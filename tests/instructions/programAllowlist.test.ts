@@ -0,0 +1,130 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  expectProposalToFail,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+// SPL Memo 程序, 本地验证器默认加载
+const MEMO_PROGRAM_ID = new PublicKey("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+describe("power-multisig: program-allowlist", () => {
+  let ctx: TestContext;
+  let memoIx: TransactionInstruction;
+
+  const addProgram = async (programId: PublicKey) =>
+    createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .addAllowedProgram(programId)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    memoIx = new TransactionInstruction({
+      programId: MEMO_PROGRAM_ID,
+      keys: [{ pubkey: ctx.vault, isSigner: true, isWritable: false }],
+      data: Buffer.from("allowlist", "utf8"),
+    });
+  });
+
+  it("places no restriction while the list is empty", async () => {
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.allowedPrograms).to.be.empty;
+
+    const proposal = await createAndExecuteProposal(ctx, memoIx);
+    const transaction = await ctx.program.account.transaction.fetch(proposal.publicKey);
+    expect(transaction.executed).to.be.true;
+  });
+
+  it("only accepts listed programs once the list is set", async () => {
+    await addProgram(SystemProgram.programId);
+
+    await expectProposalToFail(ctx, memoIx, "ProgramNotAllowed");
+
+    // 列表内的程序和本程序自身仍可调用
+    await createAndExecuteProposal(
+      ctx,
+      SystemProgram.transfer({
+        fromPubkey: ctx.vault,
+        toPubkey: ctx.owners.owner3.publicKey,
+        lamports: 1_000_000,
+      })
+    );
+    await addProgram(MEMO_PROGRAM_ID);
+    await createAndExecuteProposal(ctx, memoIx);
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.allowedPrograms.map(p => p.toBase58())).to.deep.equal([
+      SystemProgram.programId.toBase58(),
+      MEMO_PROGRAM_ID.toBase58(),
+    ]);
+  });
+
+  it("re-checks the list when an in-flight proposal executes", async () => {
+    const proposal = await proposeTransaction(ctx, [memoIx], { approvers: [ctx.owners.owner2] });
+
+    // 提案创建后才限制程序
+    await addProgram(SystemProgram.programId);
+
+    try {
+      await executeProposal(ctx, proposal, [memoIx]);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("ProgramNotAllowed");
+    }
+  });
+
+  it("lifts the restriction when the last program is removed", async () => {
+    await addProgram(SystemProgram.programId);
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .removeAllowedProgram(SystemProgram.programId)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+    await createAndExecuteProposal(ctx, memoIx);
+  });
+
+  it("rejects adding a program twice", async () => {
+    await addProgram(SystemProgram.programId);
+    try {
+      await addProgram(SystemProgram.programId);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("DuplicateProgram");
+    }
+  });
+
+  it("cannot be changed without a proposal", async () => {
+    try {
+      await ctx.program.methods
+        .addAllowedProgram(MEMO_PROGRAM_ID)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -0,0 +1,19 @@
+import { expect } from "chai";
+import { TestContext, initializeContext } from "./helper";
+
+describe("power-multisig: program-info", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await initializeContext();
+  });
+
+  it("reports the program version and supported features", async () => {
+    const info = await ctx.program.methods.getProgramInfo().view();
+
+    expect(info.version).to.equal("0.1.0");
+    // 任意指令执行 | 交易标题 | 暂停提案 | 批准凭证 | 时间锁 | 链下批准 | 自动执行 |
+    // 定期付款 | 零用额度 | 收款地址白名单 | 程序白名单 | 反对票
+    expect(info.features.toNumber()).to.equal(0b1111_1111_1111);
+  });
+});
@@ -0,0 +1,137 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: proposals-paused", () => {
+  let ctx: TestContext;
+  let pendingKey: PublicKey;
+  let receiver: anchor.web3.Keypair;
+  let transferIx: anchor.web3.TransactionInstruction;
+
+  const toProposedIx = (ix: anchor.web3.TransactionInstruction) => ({
+    programId: ix.programId,
+    accounts: ix.keys.map(key => ({
+      pubkey: key.pubkey,
+      isSigner: key.isSigner,
+      isWritable: key.isWritable,
+    })),
+    data: Buffer.from(ix.data),
+  });
+
+  const setPaused = async (paused: boolean) => {
+    const ix = await ctx.program.methods
+      .setProposalsPaused(paused)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+    await createAndExecuteProposal(ctx, ix);
+  };
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    receiver = anchor.web3.Keypair.generate();
+    transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: receiver.publicKey,
+      lamports: 0.1 * LAMPORTS_PER_SOL,
+    });
+
+    // 暂停前创建的待处理提案
+    const pending = anchor.web3.Keypair.generate();
+    pendingKey = pending.publicKey;
+    await ctx.program.methods
+      .createTransaction([toProposedIx(transferIx)], 3, 100, "", null, null, "")
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: pendingKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([pending, ctx.owners.owner1])
+      .rpc();
+
+    await setPaused(true);
+  });
+
+  it("rejects new proposals while paused", async () => {
+    const walletAccount = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(walletAccount.proposalsPaused).to.be.true;
+
+    const proposal = anchor.web3.Keypair.generate();
+    try {
+      await ctx.program.methods
+        .createTransaction([toProposedIx(transferIx)], 3, 100, "", null, null, "")
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposal.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .signers([proposal, ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed with proposals paused");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: ProposalsPaused");
+    }
+  });
+
+  it("still approves and executes pending transactions", async () => {
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: pendingKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+
+    await ctx.program.methods
+      .executeTransaction()
+      .accounts({
+        transaction: pendingKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: ctx.vault, isWritable: true, isSigner: false },
+        { pubkey: receiver.publicKey, isWritable: true, isSigner: false },
+        { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+      ])
+      .signers([ctx.owners.owner1])
+      .rpc();
+
+    const txAccount = await ctx.program.account.transaction.fetch(pendingKey);
+    expect(txAccount.executed).to.be.true;
+  });
+
+  it("can be unpaused through a proposal", async () => {
+    await setPaused(false);
+
+    const walletAccount = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(walletAccount.proposalsPaused).to.be.false;
+  });
+
+  it("cannot be toggled without the vault signature", async () => {
+    try {
+      await ctx.program.methods
+        .setProposalsPaused(false)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed the vault seeds check");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
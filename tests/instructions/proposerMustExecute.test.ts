@@ -0,0 +1,106 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: proposer-must-execute", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  let receiver: anchor.web3.Keypair;
+
+  const execute = (executor: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .executeTransaction()
+      .accounts({
+        transaction: proposalKey,
+        owner: executor.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: ctx.vault, isWritable: true, isSigner: false },
+        { pubkey: receiver.publicKey, isWritable: true, isSigner: false },
+        { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+      ])
+      .signers([executor])
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    const enableIx = await ctx.program.methods
+      .setProposerMustExecute(true)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+    await createAndExecuteProposal(ctx, enableIx);
+
+    // owner1 提案, owner2 批准
+    receiver = anchor.web3.Keypair.generate();
+    const transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: receiver.publicKey,
+      lamports: 0.1 * LAMPORTS_PER_SOL,
+    });
+    const proposal = anchor.web3.Keypair.generate();
+    proposalKey = proposal.publicKey;
+
+    await ctx.program.methods
+      .createTransaction(
+        [{
+          programId: transferIx.programId,
+          accounts: transferIx.keys.map(key => ({
+            pubkey: key.pubkey,
+            isSigner: key.isSigner,
+            isWritable: key.isWritable,
+          })),
+          data: Buffer.from(transferIx.data),
+        }],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([proposal, ctx.owners.owner1])
+      .rpc();
+
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+  });
+
+  it("rejects execution by a signer other than the proposer", async () => {
+    try {
+      await execute(ctx.owners.owner2);
+      expect.fail("should have failed with unauthorized executor");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: UnauthorizedExecutor");
+    }
+  });
+
+  it("allows the proposer to execute", async () => {
+    await execute(ctx.owners.owner1);
+
+    const txAccount = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(txAccount.executed).to.be.true;
+  });
+});
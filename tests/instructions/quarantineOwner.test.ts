@@ -0,0 +1,94 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: quarantine-owner", () => {
+  let ctx: TestContext;
+
+  const quarantineIx = (owner: PublicKey) =>
+    ctx.program.methods
+      .quarantineOwner(owner)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("zeroes the weight but keeps the owner listed", async () => {
+    await createAndExecuteProposal(ctx, await quarantineIx(ctx.owners.owner3.publicKey));
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners).to.have.length(3);
+    expect(wallet.owners[2].key.equals(ctx.owners.owner3.publicKey)).to.be.true;
+    expect(wallet.owners[2].weight.toNumber()).to.equal(0);
+    expect(wallet.ownerSetSeqno).to.equal(1);
+
+    // 被隔离的所有者不能再批准交易
+    const proposal = anchor.web3.Keypair.generate();
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner2.publicKey,
+      lamports: 1_000_000,
+    });
+    await ctx.program.methods
+      .createTransaction(
+        [{
+          programId: ix.programId,
+          accounts: ix.keys.map(key => ({
+            pubkey: key.pubkey,
+            isSigner: key.isSigner,
+            isWritable: key.isWritable,
+          })),
+          data: Buffer.from(ix.data),
+        }],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposal.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([proposal, ctx.owners.owner1])
+      .rpc();
+
+    try {
+      await ctx.program.methods
+        .approve()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposal.publicKey,
+          owner: ctx.owners.owner3.publicKey,
+        })
+        .signers([ctx.owners.owner3])
+        .rpc();
+      expect.fail("should have failed with quarantined owner");
+    } catch (error) {
+      expect(error.toString()).to.include("OwnerQuarantined");
+    }
+  });
+
+  it("refuses a quarantine that leaves the threshold unreachable", async () => {
+    try {
+      await createAndExecuteProposal(ctx, await quarantineIx(ctx.owners.owner1.publicKey));
+      expect.fail("should have failed with threshold too high");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("ThresholdTooHigh");
+    }
+  });
+});
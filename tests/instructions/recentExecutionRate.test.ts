@@ -0,0 +1,46 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: recent-execution-rate", () => {
+  let ctx: TestContext;
+
+  const rate = (window: number) =>
+    ctx.program.methods
+      .recentExecutionRate(new BN(window))
+      .accounts({ wallet: ctx.wallet.publicKey })
+      .view();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("counts recent executions and decays as the window passes", async () => {
+    expect(await rate(3600)).to.equal(0);
+
+    for (let i = 0; i < 2; i++) {
+      await createAndExecuteProposal(
+        ctx,
+        SystemProgram.transfer({
+          fromPubkey: ctx.vault,
+          toPubkey: anchor.web3.Keypair.generate().publicKey,
+          lamports: 0.01 * LAMPORTS_PER_SOL,
+        })
+      );
+    }
+    expect(await rate(3600)).to.equal(2);
+
+    // 等待时钟前进后, 较短的窗口不再包含这些执行
+    await new Promise(resolve => setTimeout(resolve, 3000));
+    expect(await rate(1)).to.equal(0);
+    expect(await rate(3600)).to.equal(2);
+  });
+});
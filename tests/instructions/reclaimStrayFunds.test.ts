@@ -0,0 +1,125 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  Transaction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+} from "./helper";
+
+describe("power-multisig: reclaim-stray-funds", () => {
+  let ctx: TestContext;
+  let proposalKey: anchor.web3.PublicKey;
+
+  const transferIx = () =>
+    SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 0.1 * LAMPORTS_PER_SOL,
+    });
+
+  // 误转到交易账户的资金
+  const sendStray = (to: anchor.web3.PublicKey, lamports: number) =>
+    ctx.provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: ctx.owners.owner1.publicKey,
+          toPubkey: to,
+          lamports,
+        })
+      ),
+      [ctx.owners.owner1]
+    );
+
+  const reclaimIx = (transaction: anchor.web3.PublicKey) =>
+    ctx.program.methods
+      .reclaimStrayFunds()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction,
+        vault: ctx.vault,
+      })
+      .instruction();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+
+    // 执行一个转账提案, 得到已执行的交易账户
+    const proposal = await createAndExecuteProposal(ctx, transferIx());
+    proposalKey = proposal.publicKey;
+  });
+
+  it("sweeps lamports above rent from an executed transaction into the vault", async () => {
+    const stray = 0.5 * LAMPORTS_PER_SOL;
+    const rentBefore = await ctx.provider.connection.getBalance(proposalKey);
+    await sendStray(proposalKey, stray);
+
+    const vaultBefore = await ctx.provider.connection.getBalance(ctx.vault);
+
+    await createAndExecuteProposal(ctx, await reclaimIx(proposalKey));
+
+    const vaultAfter = await ctx.provider.connection.getBalance(ctx.vault);
+    const transactionAfter = await ctx.provider.connection.getBalance(proposalKey);
+    expect(vaultAfter - vaultBefore).to.equal(stray);
+    expect(transactionAfter).to.equal(rentBefore);
+  });
+
+  it("sweeps lamports from a cancelled transaction", async () => {
+    // owner1(60) 反对后剩余权重 40 < 70, 交易被取消
+    const cancelled = await proposeTransaction(ctx, [transferIx()], { creator: ctx.owners.owner2 });
+    await ctx.program.methods
+      .rejectTransaction()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: cancelled,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([ctx.owners.owner1])
+      .rpc();
+
+    const stray = 0.2 * LAMPORTS_PER_SOL;
+    const rentBefore = await ctx.provider.connection.getBalance(cancelled);
+    await sendStray(cancelled, stray);
+    const vaultBefore = await ctx.provider.connection.getBalance(ctx.vault);
+
+    await createAndExecuteProposal(ctx, await reclaimIx(cancelled));
+
+    expect((await ctx.provider.connection.getBalance(ctx.vault)) - vaultBefore).to.equal(stray);
+    expect(await ctx.provider.connection.getBalance(cancelled)).to.equal(rentBefore);
+  });
+
+  it("fails when there is nothing to reclaim", async () => {
+    try {
+      await createAndExecuteProposal(ctx, await reclaimIx(proposalKey));
+      expect.fail("should have failed with no stray funds");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("NoStrayFunds");
+    }
+  });
+
+  it("cannot be called directly by an owner", async () => {
+    await sendStray(proposalKey, 0.5 * LAMPORTS_PER_SOL);
+
+    try {
+      await ctx.program.methods
+        .reclaimStrayFunds()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: proposalKey,
+          vault: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed without the vault signature");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -0,0 +1,152 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: recurring-payment", () => {
+  let ctx: TestContext;
+  let schedule: PublicKey;
+  let receiver: PublicKey;
+  const amount = 0.01 * LAMPORTS_PER_SOL;
+
+  const wait = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+  const executePayment = () =>
+    ctx.program.methods
+      .executeRecurringPayment()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        recurringPayment: schedule,
+        destination: receiver,
+      })
+      .rpc();
+
+  const closePayment = () =>
+    ctx.program.methods
+      .closeRecurringPayment()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        recurringPayment: schedule,
+      })
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    receiver = anchor.web3.Keypair.generate().publicKey;
+
+    const id = new BN(1);
+    schedule = PublicKey.findProgramAddressSync(
+      [Buffer.from("recurring"), ctx.wallet.publicKey.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
+      ctx.program.programId
+    )[0];
+
+    // 每3秒最多支付两次, 通过提案一次性批准
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .createRecurringPayment(id, {
+          destination: receiver,
+          amount: new BN(amount),
+          interval: new BN(3),
+          startTime: new BN(Math.floor(Date.now() / 1000) - 60),
+          endTime: null,
+          maxExecutions: 2,
+        })
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          recurringPayment: schedule,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+  });
+
+  it("pays on schedule until the execution limit", async () => {
+    await executePayment();
+    expect(await ctx.provider.connection.getBalance(receiver)).to.equal(amount);
+
+    try {
+      await executePayment();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("RecurringPaymentNotDue");
+    }
+
+    await wait(5000);
+    await executePayment();
+    expect(await ctx.provider.connection.getBalance(receiver)).to.equal(2 * amount);
+
+    await wait(5000);
+    try {
+      await executePayment();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("RecurringPaymentFinished");
+    }
+
+    // 用完的计划可以关闭, 租金退回 vault
+    await closePayment();
+    expect(await ctx.provider.connection.getAccountInfo(schedule)).to.be.null;
+  });
+
+  it("stops paying once cancelled through a proposal", async () => {
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .cancelRecurringPayment()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          recurringPayment: schedule,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+    try {
+      await executePayment();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("RecurringPaymentFinished");
+    }
+    await closePayment();
+  });
+
+  it("cannot be closed while active", async () => {
+    try {
+      await closePayment();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("RecurringPaymentActive");
+    }
+  });
+
+  it("cannot be created without a proposal", async () => {
+    try {
+      await ctx.program.methods
+        .createRecurringPayment(new BN(2), {
+          destination: receiver,
+          amount: new BN(amount),
+          interval: new BN(3),
+          startTime: new BN(0),
+          endTime: null,
+          maxExecutions: null,
+        })
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -0,0 +1,171 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+} from "./helper";
+
+describe("power-multisig: reject-transaction", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+
+  const reject = (owner: anchor.web3.Keypair, transaction = proposalKey) =>
+    ctx.program.methods
+      .rejectTransaction()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  const approve = (owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  const revoke = (owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .revokeSignature()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        receipt: PublicKey.findProgramAddressSync(
+          [Buffer.from("receipt"), proposalKey.toBuffer(), owner.publicKey.toBuffer()],
+          ctx.program.programId
+        )[0],
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    proposalKey = await proposeTransaction(ctx, [ix]);
+  });
+
+  it("records rejections and their weight", async () => {
+    await reject(ctx.owners.owner2);
+    await reject(ctx.owners.owner3);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.rejections.map(r => r.toBase58())).to.deep.equal([
+      ctx.owners.owner2.publicKey.toBase58(),
+      ctx.owners.owner3.publicKey.toBase58(),
+    ]);
+    expect(transaction.rejectionWeight.toNumber()).to.equal(40);
+  });
+
+  it("cancels the transaction once it can no longer reach the threshold", async () => {
+    // 剩余权重 100 - 30 = 70, 仍可达到阈值
+    await reject(ctx.owners.owner2);
+    let transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.cancelled).to.be.false;
+
+    // 剩余权重 100 - 40 = 60 < 70
+    await reject(ctx.owners.owner3);
+    transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.cancelled).to.be.true;
+    expect(transaction.rejectionWeight.toNumber()).to.equal(40);
+
+    // 取消后既不能撤回反对票, 也不能再批准
+    try {
+      await revoke(ctx.owners.owner3);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("TransactionCancelled");
+    }
+    try {
+      await approve(ctx.owners.owner3);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("TransactionCancelled");
+    }
+  });
+
+  it("rejects a second vote from the same owner", async () => {
+    await reject(ctx.owners.owner2);
+
+    try {
+      await reject(ctx.owners.owner2);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AlreadyRejected");
+    }
+
+    // 已批准的所有者(创建者)不能再反对
+    try {
+      await reject(ctx.owners.owner1);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AlreadySigned");
+    }
+  });
+
+  it("requires revoking a rejection before approving", async () => {
+    await reject(ctx.owners.owner2);
+
+    try {
+      await approve(ctx.owners.owner2);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AlreadyRejected");
+    }
+
+    await revoke(ctx.owners.owner2);
+    let transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.rejections).to.be.empty;
+    expect(transaction.rejectionWeight.toNumber()).to.equal(0);
+
+    await approve(ctx.owners.owner2);
+    transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.signers).to.have.lengthOf(2);
+  });
+
+  it("cannot reject an executed transaction", async () => {
+    const proposal = await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .setAutoExecute(false)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+    try {
+      await reject(ctx.owners.owner3, proposal.publicKey);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AlreadyExecuted");
+    }
+  });
+
+  it("rejects votes from non-owners", async () => {
+    const outsider = anchor.web3.Keypair.generate();
+    try {
+      await reject(outsider);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("NotOwner");
+    }
+  });
+});
@@ -0,0 +1,235 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+} from "./helper";
+
+describe("power-multisig: remove-owner", () => {
+  let ctx: TestContext;
+
+  const removeOwnerIx = (owner: PublicKey) =>
+    ctx.program.methods
+      .removeOwner(owner, false)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("removes an owner through an approved proposal", async () => {
+    const sizeBefore = (await ctx.provider.connection.getAccountInfo(ctx.wallet.publicKey)).data.length;
+
+    await createAndExecuteProposal(ctx, await removeOwnerIx(ctx.owners.owner3.publicKey));
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners).to.have.length(2);
+    expect(wallet.owners.some(o => o.key.equals(ctx.owners.owner3.publicKey))).to.be.false;
+    expect(wallet.ownerSetSeqno).to.equal(1);
+
+    // 释放一个所有者条目(85 字节)的空间, 租金退回 vault
+    const sizeAfter = (await ctx.provider.connection.getAccountInfo(ctx.wallet.publicKey)).data.length;
+    expect(sizeAfter).to.equal(sizeBefore - 85);
+  });
+
+  it("shrinks and re-grows the wallet account without losing data", async () => {
+    const account = () => ctx.provider.connection.getAccountInfo(ctx.wallet.publicKey);
+    const before = await account();
+
+    await createAndExecuteProposal(ctx, await removeOwnerIx(ctx.owners.owner3.publicKey));
+    const shrunk = await account();
+    expect(shrunk.data.length).to.equal(before.data.length - 85);
+    expect(shrunk.lamports).to.be.lessThan(before.lamports);
+
+    // 通过 add_owner 重新扩容, 其余字段保持不变
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .addOwner({ key: ctx.owners.owner3.publicKey, weight: new BN(10), label: "", expiresAt: null })
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction()
+    );
+    const regrown = await account();
+    expect(regrown.data.length).to.equal(before.data.length);
+    expect(regrown.lamports).to.equal(before.lamports);
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners.map(o => o.key.toBase58())).to.deep.equal([
+      ctx.owners.owner1.publicKey.toBase58(),
+      ctx.owners.owner2.publicKey.toBase58(),
+      ctx.owners.owner3.publicKey.toBase58(),
+    ]);
+    expect(wallet.thresholdWeight.toNumber()).to.equal(70);
+    expect(wallet.ownerSetSeqno).to.equal(2);
+  });
+
+  it("refuses a removal that leaves the threshold unreachable", async () => {
+    // 移除 owner1(60) 后总权重40 < 阈值70
+    try {
+      await createAndExecuteProposal(ctx, await removeOwnerIx(ctx.owners.owner1.publicKey));
+      expect.fail("should have failed with threshold too high");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("ThresholdTooHigh");
+    }
+  });
+
+  it("fails for an unknown owner", async () => {
+    try {
+      await createAndExecuteProposal(
+        ctx,
+        await removeOwnerIx(anchor.web3.Keypair.generate().publicKey)
+      );
+      expect.fail("should have failed with owner not found");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("OwnerNotFound");
+    }
+  });
+
+  it("refuses to shrink below the wallet's minimum owner count", async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx, undefined, 70, false, 3);
+
+    try {
+      await createAndExecuteProposal(ctx, await removeOwnerIx(ctx.owners.owner3.publicKey));
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("TooFewOwners");
+    }
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.minOwners).to.equal(3);
+    expect(wallet.owners).to.have.length(3);
+  });
+
+  it("drops a removed owner's approval from pending transactions", async () => {
+    // owner1(60) + owner2(30) 已满足阈值70
+    const pending = anchor.web3.Keypair.generate();
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    await ctx.program.methods
+      .createTransaction(
+        [{
+          programId: ix.programId,
+          accounts: ix.keys.map(key => ({
+            pubkey: key.pubkey,
+            isSigner: key.isSigner,
+            isWritable: key.isWritable,
+          })),
+          data: Buffer.from(ix.data),
+        }],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: pending.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([pending, ctx.owners.owner1])
+      .rpc();
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: pending.publicKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+
+    // 移除 owner2 后, 其批准不能再让交易执行
+    await createAndExecuteProposal(
+      ctx,
+      await removeOwnerIx(ctx.owners.owner2.publicKey),
+      [ctx.owners.owner1, ctx.owners.owner3]
+    );
+    try {
+      await ctx.program.methods
+        .executeTransaction()
+        .accounts({
+          transaction: pending.publicKey,
+          owner: ctx.owners.owner1.publicKey,
+        })
+        .remainingAccounts([
+          ...ix.keys.map(key => ({ ...key, isSigner: false })),
+          { pubkey: ix.programId, isWritable: false, isSigner: false },
+        ])
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("OwnerSetChanged");
+    }
+  });
+
+  it("cancels the removed owner's pending transactions when asked to", async () => {
+    const transfer = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner2.publicKey,
+      lamports: 1_000_000,
+    });
+    const byOwner3 = await proposeTransaction(ctx, [transfer], { creator: ctx.owners.owner3 });
+    const byOwner1 = await proposeTransaction(ctx, [transfer]);
+
+    // 待处理交易通过 remaining_accounts 传入, 只取消 owner3 创建的
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .removeOwner(ctx.owners.owner3.publicKey, true)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          [byOwner3, byOwner1].map(pubkey => ({ pubkey, isWritable: true, isSigner: false }))
+        )
+        .instruction()
+    );
+
+    const cancelled = await ctx.program.account.transaction.fetch(byOwner3);
+    expect(cancelled.cancelled).to.be.true;
+    const kept = await ctx.program.account.transaction.fetch(byOwner1);
+    expect(kept.cancelled).to.be.false;
+    expect(kept.executed).to.be.false;
+  });
+
+  it("cannot be called directly by an owner", async () => {
+    try {
+      await ctx.program.methods
+        .removeOwner(ctx.owners.owner3.publicKey, false)
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.owners.owner1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.owners.owner1])
+        .rpc();
+      expect.fail("should have failed without the vault signature");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
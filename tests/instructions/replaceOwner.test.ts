@@ -0,0 +1,70 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: replace-owner", () => {
+  let ctx: TestContext;
+
+  const replaceOwnerIx = (oldOwner: PublicKey, newOwner: PublicKey) =>
+    ctx.program.methods
+      .replaceOwner(oldOwner, newOwner)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+      })
+      .instruction();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("swaps the key and keeps the weight", async () => {
+    const rotated = anchor.web3.Keypair.generate();
+    await createAndExecuteProposal(
+      ctx,
+      await replaceOwnerIx(ctx.owners.owner2.publicKey, rotated.publicKey)
+    );
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners).to.have.length(3);
+    // 保持原有位置和权重
+    expect(wallet.owners[1].key.equals(rotated.publicKey)).to.be.true;
+    expect(wallet.owners[1].weight.toNumber()).to.equal(30);
+    expect(wallet.owners.some(o => o.key.equals(ctx.owners.owner2.publicKey))).to.be.false;
+    expect(wallet.ownerSetSeqno).to.equal(1);
+  });
+
+  it("rejects a key that is already an owner", async () => {
+    try {
+      await createAndExecuteProposal(
+        ctx,
+        await replaceOwnerIx(ctx.owners.owner2.publicKey, ctx.owners.owner3.publicKey)
+      );
+      expect.fail("should have failed with existing owner");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("OwnerAlreadyExists");
+    }
+  });
+
+  it("fails for an unknown owner", async () => {
+    try {
+      await createAndExecuteProposal(
+        ctx,
+        await replaceOwnerIx(
+          anchor.web3.Keypair.generate().publicKey,
+          anchor.web3.Keypair.generate().publicKey
+        )
+      );
+      expect.fail("should have failed with owner not found");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("OwnerNotFound");
+    }
+  });
+});
@@ -0,0 +1,83 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: revoke-signature", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  let transferIx: TransactionInstruction;
+
+  const revoke = (owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .revokeSignature()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        receipt: PublicKey.findProgramAddressSync(
+          [Buffer.from("receipt"), proposalKey.toBuffer(), owner.publicKey.toBuffer()],
+          ctx.program.programId
+        )[0],
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  const execute = () => executeProposal(ctx, proposalKey, [transferIx]);
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    proposalKey = await proposeTransaction(ctx, [transferIx]);
+
+    // owner1(60) + owner2(30) 达到阈值 70
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+  });
+
+  it("drops the transaction below the threshold so it cannot execute", async () => {
+    await revoke(ctx.owners.owner2);
+
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.signers.map(s => s.toBase58())).to.deep.equal([
+      ctx.owners.owner1.publicKey.toBase58(),
+    ]);
+    expect(transaction.finalSigner).to.be.null;
+
+    try {
+      await execute();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InsufficientSigners");
+    }
+  });
+
+  it("cannot revoke after execution", async () => {
+    await execute();
+
+    try {
+      await revoke(ctx.owners.owner2);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AlreadyExecuted");
+    }
+  });
+});
@@ -0,0 +1,94 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: set-owners", () => {
+  let ctx: TestContext;
+
+  const setOwnersIx = (owners: { key: PublicKey; weight: number }[], threshold: number) =>
+    ctx.program.methods
+      .setOwners(
+        owners.map(o => ({ key: o.key, weight: new BN(o.weight), label: "", expiresAt: null })),
+        new BN(threshold)
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        vault: ctx.vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+  // 每个所有者条目: key 32 + weight 8 + label 4+32 + expires_at 1+8
+  const OWNER_ENTRY_SIZE = 85;
+
+  const walletSize = async () =>
+    (await ctx.provider.connection.getAccountInfo(ctx.wallet.publicKey)).data.length;
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+  });
+
+  it("replaces the owner set with a smaller one", async () => {
+    const newOwner = anchor.web3.Keypair.generate();
+    const sizeBefore = await walletSize();
+
+    await createAndExecuteProposal(
+      ctx,
+      await setOwnersIx(
+        [
+          { key: ctx.owners.owner2.publicKey, weight: 50 },
+          { key: newOwner.publicKey, weight: 50 },
+        ],
+        60
+      )
+    );
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners).to.have.length(2);
+    expect(wallet.owners[1].key.equals(newOwner.publicKey)).to.be.true;
+    expect(wallet.thresholdWeight.toNumber()).to.equal(60);
+    expect(wallet.ownerSetSeqno).to.equal(1);
+    expect(await walletSize()).to.equal(sizeBefore - OWNER_ENTRY_SIZE);
+  });
+
+  it("grows the wallet account for a larger owner set", async () => {
+    const sizeBefore = await walletSize();
+    const owners = [
+      { key: ctx.owners.owner1.publicKey, weight: 60 },
+      { key: ctx.owners.owner2.publicKey, weight: 30 },
+      ...[0, 1, 2].map(() => ({ key: anchor.web3.Keypair.generate().publicKey, weight: 10 })),
+    ];
+
+    await createAndExecuteProposal(ctx, await setOwnersIx(owners, 80));
+
+    const wallet = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(wallet.owners).to.have.length(5);
+    expect(await walletSize()).to.equal(sizeBefore + 2 * OWNER_ENTRY_SIZE);
+  });
+
+  it("applies the create_wallet validation", async () => {
+    try {
+      await createAndExecuteProposal(
+        ctx,
+        await setOwnersIx(
+          [
+            { key: ctx.owners.owner1.publicKey, weight: 50 },
+            { key: ctx.owners.owner1.publicKey, weight: 50 },
+          ],
+          60
+        )
+      );
+      expect.fail("should have failed with duplicate owners");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("DuplicateOwner");
+    }
+  });
+});
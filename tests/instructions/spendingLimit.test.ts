@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+} from "./helper";
+
+describe("power-multisig: spending-limit", () => {
+  let ctx: TestContext;
+  let receiver: PublicKey;
+  const allowance = 0.05 * LAMPORTS_PER_SOL;
+
+  const wait = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+  const limitAddress = (owner: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("spending-limit"), ctx.wallet.publicKey.toBuffer(), owner.toBuffer()],
+      ctx.program.programId
+    )[0];
+
+  const createLimit = async (owner: PublicKey, period: number) =>
+    createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .createSpendingLimit(owner, new BN(allowance), new BN(period))
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          spendingLimit: limitAddress(owner),
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+  const spend = (owner: anchor.web3.Keypair, lamports: number) =>
+    ctx.program.methods
+      .spendFromAllowance(new BN(lamports))
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        owner: owner.publicKey,
+        destination: receiver,
+      })
+      .signers([owner])
+      .rpc();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    receiver = anchor.web3.Keypair.generate().publicKey;
+  });
+
+  it("lets an owner spend within the allowance without a proposal", async () => {
+    await createLimit(ctx.owners.owner3.publicKey, 3600);
+
+    await spend(ctx.owners.owner3, 0.03 * LAMPORTS_PER_SOL);
+    expect(await ctx.provider.connection.getBalance(receiver)).to.equal(0.03 * LAMPORTS_PER_SOL);
+
+    const limit = await ctx.program.account.spendingLimit.fetch(
+      limitAddress(ctx.owners.owner3.publicKey)
+    );
+    expect(limit.remaining.toNumber()).to.equal(0.02 * LAMPORTS_PER_SOL);
+  });
+
+  it("rejects spending beyond the allowance", async () => {
+    await createLimit(ctx.owners.owner3.publicKey, 3600);
+    await spend(ctx.owners.owner3, 0.03 * LAMPORTS_PER_SOL);
+
+    try {
+      await spend(ctx.owners.owner3, 0.03 * LAMPORTS_PER_SOL);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("SpendingLimitExceeded");
+    }
+  });
+
+  it("refills the allowance when the period rolls over", async () => {
+    await createLimit(ctx.owners.owner3.publicKey, 3);
+    await spend(ctx.owners.owner3, allowance);
+
+    await wait(5000);
+    await spend(ctx.owners.owner3, allowance);
+    expect(await ctx.provider.connection.getBalance(receiver)).to.equal(2 * allowance);
+  });
+
+  it("stops spending as soon as the limit is removed", async () => {
+    await createLimit(ctx.owners.owner3.publicKey, 3600);
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .removeSpendingLimit()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          spendingLimit: limitAddress(ctx.owners.owner3.publicKey),
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+    try {
+      await spend(ctx.owners.owner3, 0.01 * LAMPORTS_PER_SOL);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("cannot be created without a proposal", async () => {
+    try {
+      await ctx.program.methods
+        .createSpendingLimit(ctx.owners.owner3.publicKey, new BN(allowance), new BN(3600))
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          spendingLimit: limitAddress(ctx.owners.owner3.publicKey),
+          vault: ctx.owners.owner3.publicKey,
+        })
+        .signers([ctx.owners.owner3])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintSeeds");
+    }
+  });
+});
@@ -0,0 +1,208 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: temporary-owner", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  let transferIx: anchor.web3.TransactionInstruction;
+
+  const wait = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+
+    // owner2 为临时所有者, 几秒后过期
+    const now = Math.floor(Date.now() / 1000);
+    await createMultisigWallet(ctx, [
+      { key: ctx.owners.owner1.publicKey, weight: 60 },
+      { key: ctx.owners.owner2.publicKey, weight: 30, expiresAt: now + 4 },
+      { key: ctx.owners.owner3.publicKey, weight: 10 },
+    ]);
+    transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    proposalKey = await proposeTransaction(ctx, [transferIx]);
+  });
+
+  const approve = (owner: anchor.web3.Keypair) =>
+    ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  const execute = (executor = ctx.owners.owner1) =>
+    executeProposal(ctx, proposalKey, [transferIx], { executor });
+
+  it("rejects approvals from an expired owner", async () => {
+    await wait(6000);
+    try {
+      await approve(ctx.owners.owner2);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("OwnerNotFound");
+    }
+  });
+
+  it("drops an approval whose owner expires before execution", async () => {
+    await approve(ctx.owners.owner2);
+    await wait(6000);
+
+    // 签名时有效, 执行时 owner2 的权重已不再计入
+    try {
+      await execute();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InsufficientSigners");
+    }
+
+    // 其他所有者补足权重后即可执行
+    await approve(ctx.owners.owner3);
+    await execute();
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.true;
+  });
+
+  it("does not let an expired owner execute", async () => {
+    await approve(ctx.owners.owner2);
+    await approve(ctx.owners.owner3);
+    await wait(6000);
+
+    // owner1 + owner3 = 70 仍达到阈值, 但 owner2 已过期不能执行
+    try {
+      await execute(ctx.owners.owner2);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("OwnerNotFound");
+    }
+  });
+
+  it("rejects an expiry that has already passed", async () => {
+    const past = Math.floor(Date.now() / 1000) - 60;
+
+    const fresh = await initializeContext();
+    try {
+      await createMultisigWallet(fresh, [
+        { key: fresh.owners.owner1.publicKey, weight: 60 },
+        { key: fresh.owners.owner2.publicKey, weight: 30, expiresAt: past },
+        { key: fresh.owners.owner3.publicKey, weight: 10 },
+      ]);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidExpiry");
+    }
+
+    // add_owner 和 set_owners 同样拒绝; 由 owner1 + owner3 签名, 不受 owner2 过期影响
+    const newOwner = anchor.web3.Keypair.generate().publicKey;
+    const attempts = [
+      ctx.program.methods.addOwner({ key: newOwner, weight: new BN(10), label: "", expiresAt: new BN(past) }),
+      ctx.program.methods.setOwners(
+        [
+          { key: ctx.owners.owner1.publicKey, weight: new BN(60), label: "", expiresAt: null },
+          { key: newOwner, weight: new BN(30), label: "", expiresAt: new BN(past) },
+        ],
+        new BN(60)
+      ),
+    ];
+    for (const method of attempts) {
+      try {
+        await createAndExecuteProposal(
+          ctx,
+          await method
+            .accounts({
+              wallet: ctx.wallet.publicKey,
+              vault: ctx.vault,
+              systemProgram: SystemProgram.programId,
+            })
+            .instruction(),
+          [ctx.owners.owner1, ctx.owners.owner3]
+        );
+        expect.fail("Should have failed");
+      } catch (error) {
+        expect(error.logs.join("\n")).to.include("InvalidExpiry");
+      }
+    }
+  });
+
+  it("does not count expired weight when checking the threshold is reachable", async () => {
+    await wait(6000);
+
+    // owner2(30) 过期后可用权重为 60 + 10 = 70, 移除 owner3 后无法达到阈值70
+    try {
+      await createAndExecuteProposal(
+        ctx,
+        await ctx.program.methods
+          .removeOwner(ctx.owners.owner3.publicKey, false)
+          .accounts({
+            wallet: ctx.wallet.publicKey,
+            vault: ctx.vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction(),
+        [ctx.owners.owner1, ctx.owners.owner3]
+      );
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("ThresholdTooHigh");
+    }
+  });
+
+  it("does not let an expired owner change their label", async () => {
+    await wait(6000);
+    try {
+      await ctx.program.methods
+        .setOwnerLabel(ctx.owners.owner2.publicKey, "contractor")
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          owner: ctx.owners.owner2.publicKey,
+        })
+        .signers([ctx.owners.owner2])
+        .rpc();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("OwnerNotFound");
+    }
+  });
+
+  it("keeps expiries when weights change", async () => {
+    const before = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+
+    // 只修改权重, owner2 的过期时间保持不变
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .changeOwnerWeights(
+          before.owners.map(o => ({
+            key: o.key,
+            weight: new BN(o.weight.toNumber() + 10),
+          }))
+        )
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+    const after = await ctx.program.account.wallet.fetch(ctx.wallet.publicKey);
+    expect(after.owners[1].weight.toNumber()).to.equal(40);
+    expect(after.owners[1].expiresAt.toNumber()).to.equal(before.owners[1].expiresAt.toNumber());
+    expect(after.owners[0].expiresAt).to.be.null;
+  });
+});
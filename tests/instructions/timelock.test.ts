@@ -0,0 +1,149 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  createAndExecuteProposal,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: timelock", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+  let transferIx: TransactionInstruction;
+
+  const wait = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+  const propose = async (eta: number | null) => {
+    proposalKey = await proposeTransaction(ctx, [transferIx], { eta });
+  };
+
+  const execute = () => executeProposal(ctx, proposalKey, [transferIx]);
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    transferIx = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+  });
+
+  it("allows signing but not execution before the eta", async () => {
+    const eta = Math.floor(Date.now() / 1000) + 4;
+    await propose(eta);
+
+    // 时间锁期间仍可批准
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+
+    try {
+      await execute();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("TimelockNotExpired");
+    }
+
+    await wait(6000);
+    await execute();
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.true;
+    expect(transaction.eta.toNumber()).to.equal(eta);
+  });
+
+  it("applies the wallet's execution delay to every transaction", async () => {
+    await createAndExecuteProposal(
+      ctx,
+      await ctx.program.methods
+        .setExecutionDelay(new BN(4))
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          vault: ctx.vault,
+        })
+        .instruction()
+    );
+
+    await propose(null);
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+
+    try {
+      await execute();
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("TimelockNotExpired");
+    }
+
+    // 创建后等待超过延迟即可执行
+    await wait(6000);
+    await execute();
+    const transaction = await ctx.program.account.transaction.fetch(proposalKey);
+    expect(transaction.executed).to.be.true;
+    expect(transaction.createdAt.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("rejects an eta that is not in the future", async () => {
+    try {
+      await propose(Math.floor(Date.now() / 1000) - 60);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidEta");
+    }
+  });
+
+  it("lists approved transactions still waiting on their timelock", async () => {
+    const eta = Math.floor(Date.now() / 1000) + 60;
+    // locked 已达到阈值但有时间锁; ready 可立即执行; unapproved 有时间锁但权重不足
+    const locked = await proposeTransaction(ctx, [transferIx], { eta, approvers: [ctx.owners.owner2] });
+    const ready = await proposeTransaction(ctx, [transferIx], { approvers: [ctx.owners.owner2] });
+    const unapproved = await proposeTransaction(ctx, [transferIx], { eta });
+    const all = [locked, ready, unapproved];
+
+    const timelocked = (start: number, limit: number) =>
+      ctx.program.methods
+        .getTimelockedTransactions(start, limit)
+        .accounts({ wallet: ctx.wallet.publicKey })
+        .remainingAccounts(all.map(pubkey => ({ pubkey, isWritable: false, isSigner: false })))
+        .view();
+
+    const result = await timelocked(0, all.length);
+    expect(result.map(t => t.transaction.toBase58())).to.deep.equal([locked.toBase58()]);
+    expect(result[0].earliestExecutionAt.toNumber()).to.equal(eta);
+
+    // 分页只检查指定范围
+    expect(await timelocked(1, 2)).to.deep.equal([]);
+  });
+
+  it("takes the execution delay at wallet creation", async () => {
+    const fresh = await initializeContext();
+    await createMultisigWallet(fresh, undefined, 70, false, 0, false, 3600);
+    const wallet = await fresh.program.account.wallet.fetch(fresh.wallet.publicKey);
+    expect(wallet.executionDelay.toNumber()).to.equal(3600);
+
+    const negative = await initializeContext();
+    try {
+      await createMultisigWallet(negative, undefined, 70, false, 0, false, -1);
+      expect.fail("Should have failed");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidExecutionDelay");
+    }
+  });
+});
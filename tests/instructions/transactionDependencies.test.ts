@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+  proposeTransaction,
+  executeProposal,
+} from "./helper";
+
+describe("power-multisig: transaction-dependencies", () => {
+  let ctx: TestContext;
+  let receiver: anchor.web3.Keypair;
+
+  const transferIx = () =>
+    SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: receiver.publicKey,
+      lamports: 0.1 * LAMPORTS_PER_SOL,
+    });
+
+  const propose = (dependsOn: PublicKey | null, proposal = anchor.web3.Keypair.generate()) =>
+    proposeTransaction(ctx, [transferIx()], {
+      proposal,
+      dependsOn,
+      remainingAccounts: dependsOn ? [{ pubkey: dependsOn, isWritable: false, isSigner: false }] : [],
+      approvers: [ctx.owners.owner2],
+    });
+
+  const execute = (transaction: PublicKey, dependency?: PublicKey) =>
+    executeProposal(ctx, transaction, [transferIx()], {
+      extraAccounts: dependency ? [{ pubkey: dependency, isWritable: false, isSigner: false }] : [],
+    });
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    await createMultisigWallet(ctx);
+    receiver = anchor.web3.Keypair.generate();
+  });
+
+  it("blocks a dependent transaction until its dependency executes", async () => {
+    const first = await propose(null);
+    const second = await propose(first);
+
+    const info = await ctx.program.account.transaction.fetch(second);
+    expect(info.dependsOn.equals(first)).to.be.true;
+
+    try {
+      await execute(second, first);
+      expect.fail("should have failed with dependency not met");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: DependencyNotMet");
+    }
+
+    await execute(first);
+    await execute(second, first);
+
+    const secondAccount = await ctx.program.account.transaction.fetch(second);
+    expect(secondAccount.executed).to.be.true;
+  });
+
+  it("still executes after the dependency account was closed", async () => {
+    const first = await propose(null);
+    const second = await propose(first);
+
+    await execute(first);
+    await ctx.program.methods
+      .closeTransaction()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: first,
+        recipient: ctx.owners.owner1.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([ctx.owners.owner1])
+      .rpc();
+
+    await execute(second, first);
+    const secondAccount = await ctx.program.account.transaction.fetch(second);
+    expect(secondAccount.executed).to.be.true;
+  });
+
+  it("stays blocked after a cancelled dependency was closed", async () => {
+    // first 只有提案者签名, owner2 和 owner3 反对后被取消
+    const first = await proposeTransaction(ctx, [transferIx()]);
+    const second = await propose(first);
+
+    for (const owner of [ctx.owners.owner2, ctx.owners.owner3]) {
+      await ctx.program.methods
+        .rejectTransaction()
+        .accounts({
+          wallet: ctx.wallet.publicKey,
+          transaction: first,
+          owner: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+    }
+    expect((await ctx.program.account.transaction.fetch(first)).cancelled).to.be.true;
+
+    await ctx.program.methods
+      .closeTransaction()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: first,
+        recipient: ctx.owners.owner1.publicKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([ctx.owners.owner1])
+      .rpc();
+
+    try {
+      await execute(second, first);
+      expect.fail("should have failed with dependency not met");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: DependencyNotMet");
+    }
+  });
+
+  it("rejects a dependency on a transaction that does not exist", async () => {
+    const missing = anchor.web3.Keypair.generate().publicKey;
+    try {
+      await propose(missing);
+      expect.fail("should have failed with invalid dependency");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: InvalidDependency");
+    }
+  });
+
+  it("rejects a self-dependency", async () => {
+    const proposal = anchor.web3.Keypair.generate();
+    try {
+      await propose(proposal.publicKey, proposal);
+      expect.fail("should have failed with invalid dependency");
+    } catch (error) {
+      expect(error.toString()).to.include("Error Code: InvalidDependency");
+    }
+  });
+});
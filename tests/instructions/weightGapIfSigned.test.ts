@@ -0,0 +1,95 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+} from "./helper";
+
+describe("power-multisig: weight-gap-if-signed", () => {
+  let ctx: TestContext;
+  let proposalKey: PublicKey;
+
+  const gap = (owner: PublicKey) =>
+    ctx.program.methods
+      .weightGapIfSigned(owner)
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+      })
+      .view();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+    // 阈值100, owner1(60) 提案后还差40
+    await createMultisigWallet(
+      ctx,
+      [
+        { key: ctx.owners.owner1.publicKey, weight: 60 },
+        { key: ctx.owners.owner2.publicKey, weight: 30 },
+        { key: ctx.owners.owner3.publicKey, weight: 10 },
+      ],
+      100
+    );
+
+    const proposal = anchor.web3.Keypair.generate();
+    proposalKey = proposal.publicKey;
+    const ix = SystemProgram.transfer({
+      fromPubkey: ctx.vault,
+      toPubkey: ctx.owners.owner3.publicKey,
+      lamports: 1_000_000,
+    });
+    await ctx.program.methods
+      .createTransaction(
+        [{
+          programId: ix.programId,
+          accounts: ix.keys.map(key => ({
+            pubkey: key.pubkey,
+            isSigner: key.isSigner,
+            isWritable: key.isWritable,
+          })),
+          data: Buffer.from(ix.data),
+        }],
+        3,
+        100,
+        "",
+        null,
+        null,
+        ""
+      )
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner1.publicKey,
+      })
+      .signers([proposal, ctx.owners.owner1])
+      .rpc();
+  });
+
+  it("reports the remaining gap when one signature is not enough", async () => {
+    const owner2Gap = await gap(ctx.owners.owner2.publicKey);
+    expect(owner2Gap.completes).to.be.false;
+    expect(owner2Gap.remaining.toNumber()).to.equal(10);
+
+    const owner3Gap = await gap(ctx.owners.owner3.publicKey);
+    expect(owner3Gap.completes).to.be.false;
+    expect(owner3Gap.remaining.toNumber()).to.equal(30);
+  });
+
+  it("reports when a signature completes the transaction", async () => {
+    await ctx.program.methods
+      .approve()
+      .accounts({
+        wallet: ctx.wallet.publicKey,
+        transaction: proposalKey,
+        owner: ctx.owners.owner2.publicKey,
+      })
+      .signers([ctx.owners.owner2])
+      .rpc();
+
+    const owner3Gap = await gap(ctx.owners.owner3.publicKey);
+    expect(owner3Gap.completes).to.be.true;
+    expect(owner3Gap.remaining.toNumber()).to.equal(0);
+  });
+});
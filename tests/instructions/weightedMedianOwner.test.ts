@@ -0,0 +1,63 @@
+import { expect } from "chai";
+import {
+  TestContext,
+  initializeContext,
+  createMultisigWallet,
+} from "./helper";
+
+describe("power-multisig: weighted-median-owner", () => {
+  let ctx: TestContext;
+
+  const median = () =>
+    ctx.program.methods
+      .weightedMedianOwner()
+      .accounts({ wallet: ctx.wallet.publicKey })
+      .view();
+
+  beforeEach(async () => {
+    ctx = await initializeContext();
+  });
+
+  it("returns the single owner holding a majority", async () => {
+    // owner1(60) owner2(30) owner3(10)
+    await createMultisigWallet(ctx);
+
+    const pivotal = await median();
+    expect(pivotal.equals(ctx.owners.owner1.publicKey)).to.be.true;
+  });
+
+  it("breaks weight ties by pubkey", async () => {
+    // owner2(40) 之后, owner1(30) 与 owner3(30) 权重相同, 按公钥排序
+    await createMultisigWallet(
+      ctx,
+      [
+        { key: ctx.owners.owner1.publicKey, weight: 30 },
+        { key: ctx.owners.owner2.publicKey, weight: 40 },
+        { key: ctx.owners.owner3.publicKey, weight: 30 },
+      ],
+      50
+    );
+
+    const [first] = [ctx.owners.owner1.publicKey, ctx.owners.owner3.publicKey].sort(
+      (a, b) => Buffer.compare(a.toBuffer(), b.toBuffer())
+    );
+
+    const pivotal = await median();
+    expect(pivotal.equals(first)).to.be.true;
+  });
+
+  it("counts an exact half as reaching the median", async () => {
+    await createMultisigWallet(
+      ctx,
+      [
+        { key: ctx.owners.owner1.publicKey, weight: 50 },
+        { key: ctx.owners.owner2.publicKey, weight: 25 },
+        { key: ctx.owners.owner3.publicKey, weight: 25 },
+      ],
+      50
+    );
+
+    const pivotal = await median();
+    expect(pivotal.equals(ctx.owners.owner1.publicKey)).to.be.true;
+  });
+});
@@ -52,15 +52,15 @@ describe("multisig-wallet", () => {
     it("Creates a multisig wallet", async () => {
       // Create owner configurations with different weights
       const owners = [
-        { key: owner1.publicKey, weight: new BN(2) },
-        { key: owner2.publicKey, weight: new BN(2) },
-        { key: owner3.publicKey, weight: new BN(1) },
+        { key: owner1.publicKey, weight: new BN(2), label: "", expiresAt: null },
+        { key: owner2.publicKey, weight: new BN(2), label: "", expiresAt: null },
+        { key: owner3.publicKey, weight: new BN(1), label: "", expiresAt: null },
       ];
       
       const thresholdWeight = new BN(3); // Require at least weight of 3 to execute transactions
   
       await program.methods
-        .createWallet(owners, thresholdWeight)
+        .createWallet(owners, thresholdWeight, false, 0, false, new BN(0))
         .accountsPartial({
           wallet: wallet.publicKey,
           vault: walletPDA,
@@ -98,7 +98,7 @@ describe("multisig-wallet", () => {
       };
   
       await program.methods
-        .createTransaction([proposedInstruction], 3, 100)
+        .createTransaction([proposedInstruction], 3, 100, "", null, null, "")
         .accountsPartial({
           wallet: wallet.publicKey,
           transaction: transaction.publicKey,
@@ -226,7 +226,7 @@ describe("multisig-wallet", () => {
         // 创建多指令交易
         const multiTx = anchor.web3.Keypair.generate();
         await program.methods
-            .createTransaction(proposedInstructions, 5, 100)
+            .createTransaction(proposedInstructions, 5, 100, "", null, null, "")
             .accountsPartial({
                 wallet: wallet.publicKey,
                 transaction: multiTx.publicKey,